@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use nom::{IResult, branch::alt, bytes::complete::{is_not, tag}, character::complete::{char, digit1, space0}, combinator::{all_consuming, map, map_res, opt, recognize, value}, sequence::{delimited, pair, separated_pair, tuple}};
+use crate::runner::{Answer, Part};
+use crate::util::parser::ParseError;
+
+/// The known correct answers for the committed inputs, read from a manifest such as
+///
+/// ```toml
+/// [day07]
+/// part1 = 224
+/// part2 = "1488"
+/// ```
+#[derive(Debug, PartialEq, Default)]
+pub struct Answers {
+    expected: HashMap<(u32, Part), String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Mismatch { expected: String },
+    Missing,
+}
+
+fn parse_header(i: &str) -> IResult<&str, u32> {
+    delimited(
+        tag("[day"),
+        map_res(digit1, |day: &str| day.parse()),
+        char(']')
+    )(i)
+}
+
+fn parse_entry(i: &str) -> IResult<&str, (Part, &str)> {
+    separated_pair(
+        alt((
+            value(Part::One, tag("part1")),
+            value(Part::Two, tag("part2")),
+        )),
+        tuple((space0, char('='), space0)),
+        alt((
+            delimited(char('"'), is_not("\""), char('"')),
+            recognize(pair(opt(char('-')), digit1)),
+        ))
+    )(i)
+}
+
+impl Answers {
+    pub fn default_path() -> String {
+        format!("{}/answers.toml", env!("CARGO_MANIFEST_DIR"))
+    }
+
+    pub fn parse(input: &str) -> Result<Answers, ParseError> {
+        let mut answers = Answers::default();
+        let mut day = None;
+
+        for line in input.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let relocate = |error| ParseError::from_nom(input.as_bytes(), error);
+            if line.starts_with('[') {
+                day = Some(all_consuming(parse_header)(line).map_err(relocate)?.1);
+                continue;
+            }
+
+            let (part, answer) = all_consuming(map(parse_entry, |(part, answer)| (part, answer.to_owned())))(line).map_err(relocate)?.1;
+            match day {
+                Some(day) => answers.expected.insert((day, part), answer),
+                None => return Err(ParseError::at(input.as_bytes(), line.as_bytes(), "expected a [dayNN] section")),
+            };
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.expected.get(&(day, part)).map(|answer| answer.as_str())
+    }
+
+    pub fn check(&self, day: u32, answer: &Answer) -> Verdict {
+        match self.get(day, answer.part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer.value => Verdict::Pass,
+            Some(expected) => Verdict::Mismatch { expected: expected.to_owned() },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_MANIFEST: &str =
+"# answers for the committed inputs
+[day07]
+part1 = 224
+part2 = \"1488\"
+
+[day08]
+part1 = -5
+";
+
+    #[test]
+    fn will_parse_manifest() {
+        let answers = Answers::parse(EXAMPLE_MANIFEST).unwrap();
+        assert_eq!(answers.get(7, Part::One), Some("224"));
+        assert_eq!(answers.get(7, Part::Two), Some("1488"));
+        assert_eq!(answers.get(8, Part::One), Some("-5"));
+        assert_eq!(answers.get(8, Part::Two), None);
+    }
+
+    #[test]
+    fn will_locate_manifest_errors() {
+        let error = Answers::parse("[day07]\npart1 = 224\npart3 = 1").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+
+        let error = Answers::parse("part1 = 224").unwrap_err();
+        assert_eq!(error.line, 1);
+    }
+
+    #[test]
+    fn will_check_answers() {
+        let answers = Answers::parse(EXAMPLE_MANIFEST).unwrap();
+        let answer = |part, value: &str| Answer { part, value: value.to_owned() };
+
+        assert_eq!(answers.check(7, &answer(Part::One, "224")), Verdict::Pass);
+        assert_eq!(answers.check(7, &answer(Part::Two, "1")), Verdict::Mismatch { expected: "1488".to_owned() });
+        assert_eq!(answers.check(8, &answer(Part::Two, "1")), Verdict::Missing);
+    }
+}
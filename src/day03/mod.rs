@@ -0,0 +1,83 @@
+use crate::util::grid::Grid;
+use crate::util::parser::ParseError;
+use crate::solution::Solution;
+
+const TREE: u8 = b'#';
+
+/// Counts the trees hit going down the slope, where the map repeats to the right but not downwards.
+pub fn count_encountered_trees(map: &Grid<u8>, direction: (usize, usize)) -> u32 {
+    let mut position: (usize, usize) = (0, 0);
+
+    let mut tree_count = 0;
+    while position.1 < map.height() {
+        let square = *map.get_wrapping(position.0 as isize, position.1 as isize);
+        position.0 += direction.0;
+        position.1 += direction.1;
+        tree_count += if square == TREE { 1 } else { 0 };
+    };
+    return tree_count;
+}
+
+pub struct Day03;
+impl Solution for Day03 {
+    type Input<'a> = Grid<u8>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(input, |c| c == b'.' || c == TREE)
+    }
+
+    fn part1(map: &Grid<u8>) -> u32 {
+        count_encountered_trees(map, (3, 1))
+    }
+
+    fn part2(map: &Grid<u8>) -> u32 {
+        let slopes = [
+            (1, 1),
+            (3, 1),
+            (5, 1),
+            (7, 1),
+            (1, 2),
+        ];
+        slopes
+            .iter()
+            .map(|&slope| count_encountered_trees(map, slope))
+            .product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn will_count_encountered_trees() {
+        let map = Day03::parse(".#\n.#\n").unwrap();
+        assert_eq!(count_encountered_trees(&map, (0, 1)), 0);
+        assert_eq!(count_encountered_trees(&map, (1, 1)), 1);
+        assert_eq!(count_encountered_trees(&map, (3, 1)), 1);
+    }
+
+    const EXAMPLE_INPUT: &str =
+"..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day03::part1(&Day03::parse(EXAMPLE_INPUT).unwrap()), 7);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day03::part2(&Day03::parse(EXAMPLE_INPUT).unwrap()), 336);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::util::parser::{parse_all, ParseError};
+use crate::solution::Solution;
+use nom::{IResult, bytes::complete::take_while_m_n, character::complete::newline, combinator::{map, opt, recognize}, multi::many1, sequence::{pair, terminated}};
+
+type SeatId = u16;
+
+fn parse(value: &[u8]) -> SeatId {
+    let mut inverted_seat_id: SeatId = 0;
+
+    for i in 0 .. 10 {
+        inverted_seat_id |= ((value[i] as SeatId & 4) >> 2) << (9 - i);
+    }
+
+    return 1023 ^ inverted_seat_id;
+}
+
+fn parse_seat(i: &[u8]) -> IResult<&[u8], SeatId> {
+    map(
+        recognize(pair(
+            take_while_m_n(7, 7, |c| c == b'F' || c == b'B'),
+            take_while_m_n(3, 3, |c| c == b'L' || c == b'R'),
+        )),
+        parse
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn parser_works() {
+        assert_eq!(parse("FBFBBFFRLR".as_bytes()), 357);
+        assert_eq!(parse("BFFFBBFRRR".as_bytes()), 567);
+        assert_eq!(parse("FFFBBBFRRR".as_bytes()), 119);
+        assert_eq!(parse("BBFFBBFRLL".as_bytes()), 820);
+    }
+}
+
+type PreparedInput = Vec<SeatId>;
+
+pub struct Day05;
+impl Solution for Day05 {
+    type Input<'a> = PreparedInput;
+    type Output1 = SeatId;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<PreparedInput, ParseError> {
+        parse_all(input.as_bytes(), many1(terminated(parse_seat, opt(newline))))
+    }
+
+    fn part1(input: &PreparedInput) -> SeatId {
+        *input.iter().max().expect("Expected atleast one seat")
+    }
+
+    fn part2(input: &PreparedInput) -> u32 {
+        let mut min_seat_id: SeatId = SeatId::MAX;
+        let mut max_seat_id: SeatId = 0;
+        let mut sum: u32 = 0;
+
+        for &seat_id in input.iter() {
+            min_seat_id = min_seat_id.min(seat_id);
+            max_seat_id = max_seat_id.max(seat_id);
+            sum += seat_id as u32;
+        }
+
+        let expected_sum = ((min_seat_id + max_seat_id) as f32 / 2.0) * (max_seat_id - min_seat_id + 1) as f32;
+        expected_sum as u32 - sum
+    }
+}
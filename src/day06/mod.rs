@@ -0,0 +1,159 @@
+use crate::util::parser::{parse_all, ParseError};
+use crate::util::bench::Bencher;
+use crate::solution::Solution;
+use nom::{bytes::complete::take_while1, character::complete::newline, multi::{many0, many1}, sequence::terminated};
+
+fn get_bitset(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |bitset, &c| {
+        bitset | if c != b'\n' { 
+            1 << (c - b'a')
+        } else { 0 }
+    })
+}
+
+fn count_unique_characters_in_group(group: &[u8]) -> usize {
+    return get_bitset(group).count_ones() as usize;
+}
+
+fn count_shared_characters_in_group(group: &[u8]) -> usize {
+    let set: u32 = group.split(|&c| c == b'\n')
+        .map(get_bitset)
+        .fold(0xffffffff, |total_bitset, bitset| {
+            total_bitset & bitset
+        });
+
+    return set.count_ones() as usize;
+}
+
+fn iterate_and_sum(input: &str, group_calculator: fn(&[u8]) -> usize) -> usize {
+    return input.split("\n\n")
+        .map(|group| {
+            return group_calculator(group.as_bytes());
+        })
+        .sum();
+}
+
+/// Solves both parts in a single pass, combining the union and intersection of every group at once.
+pub fn together(input: &str) -> (u32, u32) {
+    input.split("\n\n")
+        .map(|group| {
+            group.as_bytes().split(|&c| c == b'\n')
+                .map(get_bitset)
+                .fold((0, std::u32::MAX), |(a, b), bitset| {
+                    (a | bitset, b & bitset)
+                })
+        })
+        .fold((0, 0), |(part1, part2), (a, b)| {
+            (part1 + a.count_ones(), part2 + b.count_ones())
+        })
+}
+
+pub fn prepare_bitsets(input: &str) -> Vec<Vec<u32>> {
+    input.split("\n\n")
+        .map(|group| {
+            group.as_bytes()
+                .split(|&c| c == b'\n')
+                .map(get_bitset)
+                .collect()
+        })
+        .collect()
+}
+
+pub fn part1_prepared_bitsets(prepared_bitsets: &[Vec<u32>]) -> usize {
+    prepared_bitsets.iter().map(|lines| {
+        lines.iter().fold(0, |acc, bitset| acc | bitset).count_ones() as usize
+    }).sum()
+}
+
+pub fn part2_prepared_bitsets(prepared_bitsets: &[Vec<u32>]) -> usize {
+    prepared_bitsets.iter().map(|lines| {
+        lines.iter().skip(1).fold(lines[0], |acc, bitset| acc & bitset).count_ones() as usize
+    }).sum()
+}
+
+pub struct Day06;
+impl Solution for Day06 {
+    type Input<'a> = &'a str;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        parse_all(input, many1(terminated(take_while1(|c: char| c.is_ascii_lowercase()), many0(newline))))?;
+        Ok(input.trim())
+    }
+
+    fn part1(input: &&str) -> usize {
+        iterate_and_sum(input, count_unique_characters_in_group)
+    }
+
+    fn part2(input: &&str) -> usize {
+        iterate_and_sum(input, count_shared_characters_in_group)
+    }
+
+    fn bench_variants(input: &str, bencher: &mut Bencher) {
+        let input = input.trim();
+        bencher.run("together", || together(input));
+
+        let prepared_bitsets = bencher.run("prepare bitsets", || prepare_bitsets(input));
+        bencher.run("part 1 - prepared bitsets", || part1_prepared_bitsets(&prepared_bitsets));
+        bencher.run("part 2 - prepared bitsets", || part2_prepared_bitsets(&prepared_bitsets));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = 
+"abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+    #[test]
+    fn will_count_unique_characters_in_group() {
+        assert_eq!(count_unique_characters_in_group("abc".as_bytes()), 3);
+        assert_eq!(count_unique_characters_in_group("a\nb\nc".as_bytes()), 3);
+        assert_eq!(count_unique_characters_in_group("ab\nac".as_bytes()), 3);
+        assert_eq!(count_unique_characters_in_group("a\na\na\na".as_bytes()), 1);
+        assert_eq!(count_unique_characters_in_group("b".as_bytes()), 1);
+    }
+    
+    #[test]
+    fn will_count_part1_example() {
+        assert_eq!(iterate_and_sum(EXAMPLE_INPUT, count_unique_characters_in_group), 11);
+    }
+    #[test]
+    fn will_count_shared_characters_in_group() {
+        assert_eq!(count_shared_characters_in_group("abc".as_bytes()), 3);
+        assert_eq!(count_shared_characters_in_group("a\nb\nc".as_bytes()), 0);
+        assert_eq!(count_shared_characters_in_group("ab\nac".as_bytes()), 1);
+        assert_eq!(count_shared_characters_in_group("a\na\na\na".as_bytes()), 1);
+        assert_eq!(count_shared_characters_in_group("b".as_bytes()), 1);
+    }
+    
+    #[test]
+    fn will_count_part2_example() {
+        assert_eq!(iterate_and_sum(EXAMPLE_INPUT, count_shared_characters_in_group), 6);
+    }
+
+    #[test]
+    fn variants_agree_on_example() {
+        assert_eq!(together(EXAMPLE_INPUT), (11, 6));
+
+        let prepared_bitsets = prepare_bitsets(EXAMPLE_INPUT);
+        assert_eq!(part1_prepared_bitsets(&prepared_bitsets), 11);
+        assert_eq!(part2_prepared_bitsets(&prepared_bitsets), 6);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,308 @@
+#![allow(unused_imports)]
+#![allow(unused_variables)]
+
+use std::str;
+use crate::util::graph::{Cycle, Direction, Graph};
+use crate::util::parser::{parse_all, ParseError};
+use crate::solution::Solution;
+
+use nom::{IResult, bytes::complete::{tag, take_while, take_while1}, character::is_alphabetic, character::{complete::{digit0, digit1, newline}, is_digit, is_space}, combinator::recognize, multi::many1, number::complete::u8, sequence::{preceded, tuple}};
+use nom::combinator::{map, map_res, opt};
+use nom::branch::alt;
+
+#[derive(Debug, PartialEq)]
+pub struct BagStatement<'a> {
+    pub color: &'a [u8],
+    pub contents: Vec<(&'a [u8], u8)>,
+}
+
+fn parse_color(input: &[u8]) -> nom::IResult<&[u8], &[u8]> {
+    recognize(
+        tuple((
+            take_while1(is_alphabetic),
+            take_while1(is_space),
+            take_while1(is_alphabetic),
+        ))
+    )(input)
+}
+
+fn parse_amount(i: &[u8]) -> IResult<&[u8], u8> {
+    map_res(digit1, |digits: &[u8]| str::from_utf8(digits).unwrap().parse())(i)
+}
+
+fn parse_bag_content_statement(i: &[u8]) -> nom::IResult<&[u8], (&[u8], u8)> {
+    let (i, amount) = parse_amount(i)?;
+    let (i, _) = take_while(is_space)(i)?;
+    let (i, color) = parse_color(i)?;
+    let (i, _) = tag(" bag")(i)?;
+    let (i, _) = opt(tag("s"))(i)?;
+    Ok((i, (color, amount)))
+}
+
+fn parse_bag_statement(i: &[u8]) -> nom::IResult<&[u8], BagStatement> {
+    let (i, color) = parse_color(i)?;
+    let (i, _) = tag(" bags contain ")(i)?;
+    let (i, contents ) = alt((
+        map(tag("no other bags"), |_| vec![]),
+        many1(preceded(opt(tag(", ")), map(
+            parse_bag_content_statement,
+            |(color, amount)| (color.into(), amount)
+        )))
+    ))(i)?;
+
+    let (i, _) = tag(".")(i)?;
+    let (i, _) = opt(newline)(i)?;
+
+    Ok((i, BagStatement {
+        color: color.into(),
+        contents,
+    }))
+}
+
+/// Bag colours, with an edge to every colour a bag directly contains weighted by how many it holds.
+pub type Rules<'a> = Graph<&'a [u8], u32>;
+
+type PreparedInput<'a> = Rules<'a>;
+
+const SHINY_GOLD: &[u8] = b"shiny gold";
+
+fn build_rules<'a>(statements: &[BagStatement<'a>]) -> Rules<'a> {
+    let mut rules = Graph::new();
+    for statement in statements {
+        let color = rules.intern(statement.color);
+        for &(content, amount) in &statement.contents {
+            let content = rules.intern(content);
+            rules.add_edge(color, content, amount as u32);
+        }
+    }
+    rules
+}
+
+/// How many colours of bag can eventually hold a bag of colour `color`.
+pub fn count_containers(rules: &Rules, color: usize) -> usize {
+    rules.reachable(color, Direction::Reverse).len()
+}
+
+/// How many bags a bag of colour `color` holds, including the bags inside those. Fails when the bag can end up
+/// inside itself.
+pub fn count_inside(rules: &Rules, color: usize) -> Result<u32, Cycle> {
+    rules.fold(color, Direction::Forward, |_, contents: &[(u32, u32)]| {
+        contents.iter().map(|&(inside, amount)| amount * (inside + 1)).sum()
+    })
+}
+
+/// Every colour of bag that can eventually hold a bag of colour `color`, sorted by name.
+pub fn containers<'a>(rules: &Rules<'a>, color: usize) -> Vec<&'a [u8]> {
+    let mut colors: Vec<&[u8]> = rules.reachable(color, Direction::Reverse).into_iter().map(|id| *rules.key(id)).collect();
+    colors.sort_unstable();
+    colors
+}
+
+/// How many bags of each colour a bag of colour `color` holds, directly or nested, with the most common first.
+/// Fails when the bag can end up inside itself.
+pub fn inside_by_color<'a>(rules: &Rules<'a>, color: usize) -> Result<Vec<(&'a [u8], u32)>, Cycle> {
+    let order = rules.topological_order(color, Direction::Forward)?;
+    let mut counts = vec![0; rules.len()];
+    counts[color] = 1;
+    for &id in &order {
+        for &(content, amount) in rules.edges(id, Direction::Forward) {
+            counts[content] += counts[id] * amount;
+        }
+    }
+
+    let mut inside: Vec<(&[u8], u32)> = order[1..].iter().map(|&id| (*rules.key(id), counts[id])).collect();
+    inside.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    Ok(inside)
+}
+
+/// The shortest chain of bags from `outer` down to `inner`, each with how many of it the bag before holds. The
+/// outer bag itself comes first with a count of 1.
+pub fn containment_path<'a>(rules: &Rules<'a>, outer: usize, inner: usize) -> Option<Vec<(&'a [u8], u32)>> {
+    let path = rules.shortest_path(outer, inner, Direction::Forward)?;
+    let amount = |(holder, content): (usize, usize)| {
+        rules.edges(holder, Direction::Forward).iter().find(|&&(id, _)| id == content).map_or(0, |&(_, amount)| amount)
+    };
+
+    let mut chain = vec![(*rules.key(outer), 1)];
+    chain.extend(path.windows(2).map(|pair| (*rules.key(pair[1]), amount((pair[0], pair[1])))));
+    Some(chain)
+}
+
+pub struct Day07;
+impl Solution for Day07 {
+    type Input<'a> = PreparedInput<'a>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<PreparedInput<'_>, ParseError> {
+        let statements = parse_all(input.as_bytes(), many1(parse_bag_statement))?;
+        let rules = build_rules(&statements);
+        match rules.topological_sort(Direction::Forward) {
+            Ok(_) => Ok(rules),
+            Err(Cycle { node }) => Err(ParseError::at(input.as_bytes(), rules.key(node), "bag can end up inside itself")),
+        }
+    }
+
+    fn part1(input: &PreparedInput) -> u32 {
+        input.id(SHINY_GOLD).map_or(0, |color| count_containers(input, color)) as u32
+    }
+
+    fn part2(input: &PreparedInput) -> u32 {
+        input.id(SHINY_GOLD).map_or(0, |color| count_inside(input, color).expect("Parsed rules should not have a cycle"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::error::ErrorKind;
+
+    use super::*;
+
+    #[test]
+    fn will_parse_color() {
+        assert_eq!(parse_color("light red bags".as_bytes()), Ok((r" bags".as_bytes(), "light red".as_bytes())));
+    }
+
+    #[test]
+    fn will_parse_amount() {
+        assert_eq!(
+            parse_amount(r"0".as_bytes()), 
+            Ok((
+                r"".as_bytes(), 
+                0
+            ))
+        );
+        assert_eq!(
+            parse_amount(r"123".as_bytes()), 
+            Ok((
+                r"".as_bytes(), 
+                123
+            ))
+        );
+    }
+
+    #[test]
+    fn will_parse_bag_content_statement() {
+        assert_eq!(
+            parse_bag_content_statement(r"1 bright white bag".as_bytes()), 
+            Ok((
+                r"".as_bytes(), 
+                (r"bright white".as_bytes(), 1)
+            ))
+        );
+    }
+
+    #[test]
+    fn will_parse_bag_statement() {
+        assert_eq!(
+            parse_bag_statement(r"light red bags contain 1 bright white bag, 2 muted yellow bags.".as_bytes()), 
+            Ok((
+                r"".as_bytes(), 
+                BagStatement {
+                    color: r"light red".as_bytes().into(),
+                    contents: vec![
+                        (r"bright white".as_bytes().into(), 1),
+                        (r"muted yellow".as_bytes().into(), 2),
+                    ]
+                }
+            ))
+        );
+
+        assert_eq!(
+            parse_bag_statement(r"light red bags contain no other bags.".as_bytes()), 
+            Ok((
+                r"".as_bytes(), 
+                BagStatement {
+                    color: r"light red".as_bytes().into(),
+                    contents: vec![]
+                }
+            ))
+        );
+    }
+
+    const EXAMPLE_INPUT: &str = 
+"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+    
+    #[test]
+    fn will_count_part1_example() {
+        assert_eq!(Day07::part1(&Day07::parse(EXAMPLE_INPUT).unwrap()), 4);
+    }
+    
+    #[test]
+    fn will_query_any_color() {
+        let rules = Day07::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(count_containers(&rules, rules.id("faded blue".as_bytes()).unwrap()), 7);
+        assert_eq!(count_containers(&rules, rules.id("light red".as_bytes()).unwrap()), 0);
+        assert_eq!(count_inside(&rules, rules.id("dark olive".as_bytes()).unwrap()), Ok(7));
+    }
+
+    #[test]
+    fn will_list_containers_and_contents() {
+        let rules = Day07::parse(EXAMPLE_INPUT).unwrap();
+        let shiny_gold = rules.id(SHINY_GOLD).unwrap();
+        assert_eq!(containers(&rules, shiny_gold), vec![&b"bright white"[..], b"dark orange", b"light red", b"muted yellow"]);
+
+        let inside = inside_by_color(&rules, shiny_gold).unwrap();
+        assert_eq!(inside, vec![(&b"dotted black"[..], 16), (b"faded blue", 13), (b"vibrant plum", 2), (b"dark olive", 1)]);
+        assert_eq!(Ok(inside.iter().map(|(_, count)| count).sum::<u32>()), count_inside(&rules, shiny_gold));
+    }
+
+    #[test]
+    fn will_find_containment_path() {
+        let rules = Day07::parse(EXAMPLE_INPUT).unwrap();
+        let id = |color: &str| rules.id(color.as_bytes()).unwrap();
+        assert_eq!(
+            containment_path(&rules, id("light red"), id("dark olive")),
+            Some(vec![(&b"light red"[..], 1), (b"bright white", 1), (b"shiny gold", 1), (b"dark olive", 1)])
+        );
+        assert_eq!(containment_path(&rules, id("faded blue"), id("shiny gold")), None);
+    }
+
+    #[test]
+    fn will_reject_cyclic_rules() {
+        let input = "light red bags contain 1 shiny gold bag.\nshiny gold bags contain 1 dark red bag.\ndark red bags contain 2 shiny gold bags.";
+        let error = Day07::parse(input).unwrap_err();
+        assert_eq!(error.message, "bag can end up inside itself");
+        assert!(error.snippet.starts_with("shiny gold") || error.snippet.starts_with("dark red"));
+
+        let statements = parse_all(input.as_bytes(), many1(parse_bag_statement)).unwrap();
+        let rules = build_rules(&statements);
+        let light_red = rules.id("light red".as_bytes()).unwrap();
+        assert!(count_inside(&rules, light_red).is_err());
+        assert!(inside_by_color(&rules, light_red).is_err());
+        assert_eq!(count_containers(&rules, light_red), 0);
+    }
+
+    #[test]
+    fn will_count_nothing_without_shiny_gold() {
+        let rules = Day07::parse("light red bags contain 2 dark red bags.\ndark red bags contain no other bags.").unwrap();
+        assert_eq!((Day07::part1(&rules), Day07::part2(&rules)), (0, 0));
+    }
+
+    #[test]
+    fn will_count_part2_example() {
+        assert_eq!(Day07::part2(&Day07::parse(EXAMPLE_INPUT).unwrap()), 32);
+    }
+
+    const EXAMPLE_INPUT2: &str = 
+"shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+    
+    #[test]
+    fn will_count_part2_example2() {
+        assert_eq!(Day07::part2(&Day07::parse(EXAMPLE_INPUT2).unwrap()), 126);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+use crate::util::parser::ParseError;
+use crate::solution::Solution;
+use crate::util::bench::Bencher;
+use crate::vm::{self, repair, Halt, Machine, Program};
+
+type PreparedInput = Program;
+
+pub struct Day08;
+impl Solution for Day08 {
+    type Input<'a> = PreparedInput;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<PreparedInput, ParseError> {
+        vm::parse_program(input)
+    }
+
+    fn part1(input: &PreparedInput) -> i32 {
+        let mut machine = Machine::new(input);
+        let halt = machine.run();
+        assert!(matches!(halt, Halt::InfiniteLoop { .. }), "Expected an infinite loop, got {:?}", halt);
+        machine.accumulator()
+    }
+
+    fn part2(input: &PreparedInput) -> i32 {
+        repair::repair_linear(input).expect("Expected a single flip to repair the program").accumulator
+    }
+
+    fn bench_variants(input: &str, bencher: &mut Bencher) {
+        if let Ok(program) = Day08::parse(input) {
+            bencher.run("part 2 - brute force", || repair::repair_brute_force(&program));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::EXAMPLE;
+    
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day08::part1(&Day08::parse(EXAMPLE).unwrap()), 5);
+    }
+    
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day08::part2(&Day08::parse(EXAMPLE).unwrap()), 8);
+    }
+    
+    #[test]
+    fn part2_flip_jump() {
+        assert_eq!(Day08::part2(&Day08::parse(
+"jmp +0"
+        ).unwrap()), 0);
+    }
+
+    #[test]
+    fn parse_error_is_located() {
+        let error = Day08::parse("nop +0\nacc +1\nfoo +4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.snippet, "foo +4");
+    }
+}
\ No newline at end of file
@@ -1,19 +1,14 @@
-use std::{collections::{HashSet, VecDeque}, fs};
-use aoc_2020_rust::util::bench;
-use aoc_2020_rust::util::parser;
-
-use nom::{character::complete::newline, combinator::{all_consuming, opt}, multi::many1, sequence::terminated};
-
-fn prepare_input(input: &str) -> Vec<u64> {
-    all_consuming(many1(
-        terminated(
-            parser::parse_decimal_u64,
-            opt(newline)
-        )
-    ))(input.as_bytes()).expect("").1
-}
+use std::collections::{HashSet, VecDeque};
+use crate::util::parser::{self, parse_all, ParseError};
+use crate::solution::Solution;
+
+use nom::{character::complete::newline, combinator::opt, multi::many1, sequence::terminated};
+
+type PreparedInput = Vec<u64>;
+
+const PREAMBLE_LEN: usize = 25;
 
-fn part1(input: &Vec<u64>, preamble_len: usize) -> u64 {
+fn find_invalid_number(input: &PreparedInput, preamble_len: usize) -> u64 {
     let mut working_set: HashSet<u64> = HashSet::new();
     let mut working_vec: VecDeque<u64> = VecDeque::new();
 
@@ -39,8 +34,8 @@ fn part1(input: &Vec<u64>, preamble_len: usize) -> u64 {
     });
     *found_nonexistent_sum.unwrap()
 }
-fn part2(input: &Vec<u64>, preamble_len: usize) -> u64 {
-    let expected_sum = part1(input, preamble_len);
+fn find_encryption_weakness(input: &PreparedInput, preamble_len: usize) -> u64 {
+    let expected_sum = find_invalid_number(input, preamble_len);
 
     let mut working_vec: VecDeque<u64> = VecDeque::new();
     let mut current_sum: u64 = 0;
@@ -62,15 +57,28 @@ fn part2(input: &Vec<u64>, preamble_len: usize) -> u64 {
     min + max
 }
 
-fn main() {
-    let input = fs::read_to_string("./src/day09/input.txt").expect("Unable to read input file");
+pub struct Day09;
+impl Solution for Day09 {
+    type Input<'a> = PreparedInput;
+    type Output1 = u64;
+    type Output2 = u64;
 
-    let prepared_input = bench::time("input preparation", || prepare_input(&input));
-    let part1 = bench::time("part 1", || part1(&prepared_input, 25));
-    let part2 = bench::time("part 2", || part2(&prepared_input, 25));
+    fn parse(input: &str) -> Result<PreparedInput, ParseError> {
+        parse_all(input.as_bytes(), many1(
+            terminated(
+                parser::parse_decimal_u64,
+                opt(newline)
+            )
+        ))
+    }
+
+    fn part1(input: &PreparedInput) -> u64 {
+        find_invalid_number(input, PREAMBLE_LEN)
+    }
 
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
+    fn part2(input: &PreparedInput) -> u64 {
+        find_encryption_weakness(input, PREAMBLE_LEN)
+    }
 }
 
 #[cfg(test)]
@@ -101,11 +109,11 @@ mod tests {
     
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&prepare_input(EXAMPLE_INPUT), 5), 127);
+        assert_eq!(find_invalid_number(&Day09::parse(EXAMPLE_INPUT).unwrap(), 5), 127);
     }
     
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&prepare_input(EXAMPLE_INPUT), 5), 62);
+        assert_eq!(find_encryption_weakness(&Day09::parse(EXAMPLE_INPUT).unwrap(), 5), 62);
     }
 }
\ No newline at end of file
@@ -0,0 +1,97 @@
+use crate::util::automaton::{Automaton, GridTopology};
+use crate::util::cycle::Outcome;
+use crate::util::grid::{Grid, Neighbourhood};
+use crate::util::parser::ParseError;
+use crate::solution::Solution;
+
+const SEAT: u8 = b'L';
+const MAX_ROUNDS: usize = 10_000;
+
+/// Fills the seats until nobody moves anymore: an empty seat is taken when none of its neighbouring seats are
+/// occupied, and an occupied seat is left once `tolerance` or more of them are.
+fn count_settled_seats<F>(map: &Grid<u8>, tolerance: usize, neighbouring_seats: F) -> usize
+where F: Fn(usize, usize) -> Vec<(usize, usize)>
+{
+    let topology = GridTopology::with_neighbours(map.width(), map.height(), |x, y| {
+        if map[(x, y)] == SEAT { Some(neighbouring_seats(x, y)) } else { None }
+    });
+    let rule = |occupied, occupied_neighbours| match occupied {
+        false => occupied_neighbours == 0,
+        true => occupied_neighbours < tolerance,
+    };
+
+    let automaton = Automaton::new(topology, rule, Grid::filled(map.width(), map.height(), false));
+    let repetition = automaton.detect_repetition(MAX_ROUNDS)
+        .unwrap_or_else(|| panic!("Seating did not settle within {} rounds", MAX_ROUNDS));
+    match repetition.outcome() {
+        Outcome::Fixpoint { step } => repetition.state_at(step).cells().filter(|&&occupied| occupied).count(),
+        Outcome::Cycle { start, length } => panic!("Seating never settles, it repeats every {} rounds from round {}", length, start),
+    }
+}
+
+pub struct Day11;
+impl Solution for Day11 {
+    type Input<'a> = Grid<u8>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(input, |c| c == SEAT || c == b'.')
+    }
+
+    fn part1(input: &Grid<u8>) -> usize {
+        count_settled_seats(input, 4, |x, y| {
+            input.neighbour_positions(x, y, Neighbourhood::Surrounding).filter(|&position| input[position] == SEAT).collect()
+        })
+    }
+
+    fn part2(input: &Grid<u8>) -> usize {
+        count_settled_seats(input, 5, |x, y| {
+            input.visible(x, y, Neighbourhood::Surrounding, |&square| square == SEAT).map(|(position, _)| position).collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = 
+"L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+    
+    #[test]
+    fn parse_example_map() {
+        let map = Day11::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(map.width(), 10);
+        assert_eq!(map.height(), 10);
+        assert_eq!(map.get(0, 0), Some(&b'L'));
+        assert_eq!(map.get(9, 0), Some(&b'L'));
+        assert_eq!(map.get(0, 1), Some(&b'L'));
+        assert_eq!(map.get(9, 9), Some(&b'L'));
+    }
+    
+    #[test]
+    fn map_iter() {
+        let map = Day11::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(map.iter().count(), 10*10);
+    }
+    
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day11::part1(&Day11::parse(EXAMPLE_INPUT).unwrap()), 37);
+    }
+    
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day11::part2(&Day11::parse(EXAMPLE_INPUT).unwrap()), 26);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use core::fmt::Debug;
+use crate::util::parser::{parse_all, parse_decimal_u32, ParseError};
+use crate::solution::Solution;
+use nom::{IResult, branch::alt, bytes::complete::tag, character::complete::newline, combinator::{map, opt}, multi::many1, sequence::{pair, terminated}};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum InstructionKind {
+    NORTH,
+    SOUTH,
+    EAST,
+    WEST,
+    LEFT,
+    RIGHT,
+    FORWARD,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Instruction {
+    pub kind: InstructionKind,
+    pub amount: i32,
+}
+
+fn parse_instruction(i: &[u8]) -> IResult<&[u8], Instruction> {
+    map(
+        pair(
+            alt((
+                map(tag("N"), |_| InstructionKind::NORTH),
+                map(tag("S"), |_| InstructionKind::SOUTH),
+                map(tag("E"), |_| InstructionKind::EAST),
+                map(tag("W"), |_| InstructionKind::WEST),
+                map(tag("L"), |_| InstructionKind::LEFT),
+                map(tag("R"), |_| InstructionKind::RIGHT),
+                map(tag("F"), |_| InstructionKind::FORWARD),
+            )),
+            parse_decimal_u32
+        ),
+        |(kind, number)| -> Instruction {
+            Instruction {
+                kind,
+                amount: number as i32
+            }
+        }
+    )(i)
+}
+
+type PreparedInput = Vec<Instruction>;
+
+pub struct Day12;
+impl Solution for Day12 {
+    type Input<'a> = PreparedInput;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<PreparedInput, ParseError> {
+        parse_all(input.as_bytes(), many1(
+            terminated(
+                parse_instruction,
+                opt(newline)
+            )
+        ))
+    }
+
+    fn part1(input: &PreparedInput) -> i32 {
+        struct State {
+            x: i32,
+            y: i32,
+            direction: i32,
+        }
+    
+        fn apply(state: &mut State, instruction: &Instruction) {
+            match instruction.kind {
+                InstructionKind::EAST => { state.x += instruction.amount }
+                InstructionKind::WEST => { state.x -= instruction.amount }
+                InstructionKind::NORTH => { state.y += instruction.amount }
+                InstructionKind::SOUTH => { state.y -= instruction.amount }
+                InstructionKind::LEFT => { state.direction += instruction.amount }
+                InstructionKind::RIGHT => { state.direction -= instruction.amount }
+                InstructionKind::FORWARD => {
+                    let normalized = state.direction % 360 + if state.direction % 360 < 0 { 360 } else { 0 };
+    
+                    match normalized {
+                        0 => state.x += instruction.amount,
+                        90 => state.y += instruction.amount,
+                        180 => state.x -= instruction.amount,
+                        270 => state.y -= instruction.amount,
+                        _ => panic!("Direction not exactly at 90 degree angle {}", normalized),
+                    }
+                }
+            };
+        }
+
+        let mut state = State {
+            x: 0,
+            y: 0,
+            direction: 0,
+        };
+
+        input.iter().for_each(|instruction| {
+            apply(&mut state, instruction);
+        });
+
+        state.x.abs() + state.y.abs()
+    }
+
+    fn part2(input: &PreparedInput) -> i32 {
+
+        #[derive(Debug)]
+        struct State {
+            x: i32,
+            y: i32,
+            wp_x: i32,
+            wp_y: i32,
+        }
+    
+        fn apply(state: &mut State, instruction: &Instruction) {
+            match instruction.kind {
+                InstructionKind::EAST => { state.wp_x += instruction.amount }
+                InstructionKind::WEST => { state.wp_x -= instruction.amount }
+                InstructionKind::NORTH => { state.wp_y += instruction.amount }
+                InstructionKind::SOUTH => { state.wp_y -= instruction.amount }
+                InstructionKind::LEFT => {
+                    for _ in 0..(instruction.amount / 90) {
+                        // 0 -1
+                        // 1  0
+                        let (wp_x, wp_y) = (state.wp_x, state.wp_y);
+                        state.wp_x = -wp_y;
+                        state.wp_y = wp_x;
+                    }
+                }
+                InstructionKind::RIGHT => {
+                    for _ in 0..(instruction.amount / 90) {
+                        // 0  1
+                        // -1 0
+                        let (wp_x, wp_y) = (state.wp_x, state.wp_y);
+                        state.wp_x = wp_y;
+                        state.wp_y = -wp_x;
+                    }
+                }
+                InstructionKind::FORWARD => {
+                    state.x += state.wp_x * instruction.amount;
+                    state.y += state.wp_y * instruction.amount;
+                }
+            };
+        }
+
+        let mut state = State {
+            x: 0,
+            y: 0,
+            wp_x: 10,
+            wp_y: 1,
+        };
+
+        input.iter().for_each(|instruction| {
+            apply(&mut state, instruction);
+        });
+
+        state.x.abs() + state.y.abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn will_parse_instruction() {
+        assert_eq!(
+            parse_instruction(r"F10".as_bytes()), 
+            Ok((
+                r"".as_bytes(), 
+                Instruction { kind: InstructionKind::FORWARD, amount: 10 }
+            ))
+        );
+        assert_eq!(
+            parse_instruction(r"R90".as_bytes()), 
+            Ok((
+                r"".as_bytes(), 
+                Instruction { kind: InstructionKind::RIGHT, amount: 90 }
+            ))
+        );
+    }
+
+    const EXAMPLE_INPUT: &str = 
+"F10
+N3
+F7
+R90
+F11";
+    
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day12::part1(&Day12::parse(EXAMPLE_INPUT).unwrap()), 25);
+    }
+    
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day12::part2(&Day12::parse(EXAMPLE_INPUT).unwrap()), 286);
+    }
+}
\ No newline at end of file
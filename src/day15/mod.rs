@@ -1,5 +1,7 @@
 use std::collections::HashMap;
-use aoc_2020_rust::util::bench;
+use crate::util::parser::{parse_all, parse_decimal_u32, ParseError};
+use crate::solution::Solution;
+use nom::{character::complete::{char, newline}, combinator::opt, multi::separated_list1, sequence::terminated};
 
 fn run(input: &Vec<u32>, number: usize) -> u32 {
     const LIMIT: u32 = 1 << 22;
@@ -30,13 +32,23 @@ fn run(input: &Vec<u32>, number: usize) -> u32 {
     })
 }
 
-fn main() {
-    let input = vec![1, 0, 15, 2, 10, 13];
-    let part1 = bench::time("part 1", || run(&input, 2020));
-    let part2 = bench::time("part 2", || run(&input, 30000000));
+pub struct Day15;
+impl Solution for Day15 {
+    type Input<'a> = Vec<u32>;
+    type Output1 = u32;
+    type Output2 = u32;
 
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse_all(input.as_bytes(), terminated(separated_list1(char(','), parse_decimal_u32), opt(newline)))
+    }
+
+    fn part1(input: &Vec<u32>) -> u32 {
+        run(input, 2020)
+    }
+
+    fn part2(input: &Vec<u32>) -> u32 {
+        run(input, 30000000)
+    }
 }
 
 #[cfg(test)]
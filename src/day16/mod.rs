@@ -1,5 +1,5 @@
-use std::fs;
-use aoc_2020_rust::util::{bench, parser::parse_decimal_u64, bitset::SmallIntegerSet64};
+use crate::util::{parser::{parse_all, parse_decimal_u64, ParseError}, bitset::SmallIntegerSet64};
+use crate::solution::Solution;
 use nom::{IResult, bytes::complete::{is_not, tag}, character::complete::{char, newline}, combinator::opt, multi::many1, sequence::{preceded, separated_pair, terminated}};
 
 type Range = (u64, u64);
@@ -8,10 +8,10 @@ type Fields<'a> = Vec<FieldAndRanges<'a>>;
 type Ticket = Vec<u64>;
 
 #[derive(Debug, PartialEq)]
-struct PreparedInput<'a> {
-    fields: Vec<FieldAndRanges<'a>>,
-    your_ticket: Ticket,
-    nearby_tickets: Vec<Ticket>,
+pub struct PreparedInput<'a> {
+    pub fields: Vec<FieldAndRanges<'a>>,
+    pub your_ticket: Ticket,
+    pub nearby_tickets: Vec<Ticket>,
 }
 
 fn parse_range(i: &[u8]) -> IResult<&[u8], Range> {
@@ -63,7 +63,7 @@ fn parse_nearby_tickets(i: &[u8]) -> IResult<&[u8], Vec<Ticket>> {
     )(i)
 }
 
-fn parse(i: &[u8]) -> IResult<&[u8], PreparedInput> {
+fn parse_input(i: &[u8]) -> IResult<&[u8], PreparedInput> {
     let (i, fields) = parse_fields(i)?;
     let (i, _) = newline(i)?;
     let (i, your_ticket) = parse_your_ticket(i)?;
@@ -71,7 +71,6 @@ fn parse(i: &[u8]) -> IResult<&[u8], PreparedInput> {
     let (i, _) = newline(i)?;
     let (i, nearby_tickets) = parse_nearby_tickets(i)?;
 
-    assert!(i.len() == 0);
     Ok((i, PreparedInput {
         fields,
         your_ticket,
@@ -79,10 +78,6 @@ fn parse(i: &[u8]) -> IResult<&[u8], PreparedInput> {
     }))
 }
 
-fn prepare_input(input: &str) -> PreparedInput {
-    parse(input.as_bytes()).unwrap().1
-}
-
 fn create_valid_map(fields: &Fields) -> [bool;1000] {
     fields.iter().fold(
         [false; 1000], 
@@ -113,16 +108,6 @@ fn create_number_to_valid_fields(fields: &Fields) -> Vec<SmallIntegerSet64> {
     )
 }
 
-fn part1(input: &PreparedInput) -> u64 {
-    let valid = create_valid_map(&input.fields);
-
-    input.nearby_tickets.iter().flat_map(|ticket| {
-        ticket.iter()
-    }).filter(|&&number| {
-        !valid[number as usize]
-    }).sum()
-}
-
 fn validate(input: &PreparedInput, column_to_field: &Vec<usize>) {
     let number_to_valid_fields = create_number_to_valid_fields(&input.fields);
 
@@ -138,7 +123,7 @@ fn validate(input: &PreparedInput, column_to_field: &Vec<usize>) {
     });
 }
 
-fn part2(input: &PreparedInput) -> Vec<u64> {
+fn your_ticket_by_field(input: &PreparedInput) -> Vec<u64> {
     let number_to_valid_fields = create_number_to_valid_fields(&input.fields);
 
     let filtered_tickets = input.nearby_tickets.iter().filter(|ticket| {
@@ -184,20 +169,32 @@ fn part2(input: &PreparedInput) -> Vec<u64> {
     }).collect()
 }
 
-fn main() {
-    let input = fs::read_to_string("./src/day16/input.txt").expect("Unable to read input file");
+pub struct Day16;
+impl Solution for Day16 {
+    type Input<'a> = PreparedInput<'a>;
+    type Output1 = u64;
+    type Output2 = u64;
 
-    let prepared_input = bench::time("input preparation", || prepare_input(&input));
-    let part1 = bench::time("part 1", || part1(&prepared_input));
-    let part2 = bench::time(
-        "part 2", 
-        || part2(&prepared_input).iter().take(6).fold(1, |mult: u64, &number| {
-            mult * number as u64
-        })
-    );
+    fn parse(input: &str) -> Result<PreparedInput<'_>, ParseError> {
+        parse_all(input.as_bytes(), parse_input)
+    }
+
+    fn part1(input: &PreparedInput) -> u64 {
+        let valid = create_valid_map(&input.fields);
 
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
+        input.nearby_tickets.iter().flat_map(|ticket| {
+            ticket.iter()
+        }).filter(|&&number| {
+            !valid[number as usize]
+        }).sum()
+    }
+
+    fn part2(input: &PreparedInput) -> u64 {
+        your_ticket_by_field(input).iter().zip(input.fields.iter())
+            .filter(|(_, (name, _))| name.starts_with(b"departure"))
+            .map(|(&number, _)| number)
+            .product()
+    }
 }
 
 #[cfg(test)]
@@ -225,7 +222,7 @@ nearby tickets:
         
         assert_eq!(parse_ticket("7,1,14".as_bytes()), Ok(("".as_bytes(), vec![7, 1, 14])));
 
-        assert_eq!(prepare_input(EXAMPLE_INPUT1), PreparedInput {
+        assert_eq!(Day16::parse(EXAMPLE_INPUT1).unwrap(), PreparedInput {
             fields: vec![
                 ("class".as_bytes(), ((1,3), (5,7))),
                 ("row".as_bytes(), ((6,11), (33,44))),
@@ -243,7 +240,7 @@ nearby tickets:
     
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&prepare_input(EXAMPLE_INPUT1)), 71);
+        assert_eq!(Day16::part1(&Day16::parse(EXAMPLE_INPUT1).unwrap()), 71);
     }
 
     const EXAMPLE_INPUT2: &str = 
@@ -261,6 +258,6 @@ nearby tickets:
     
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&prepare_input(EXAMPLE_INPUT2)), vec![12, 11, 13]);
+        assert_eq!(your_ticket_by_field(&Day16::parse(EXAMPLE_INPUT2).unwrap()), vec![12, 11, 13]);
     }
 }
\ No newline at end of file
@@ -0,0 +1,399 @@
+use std::collections::HashSet;
+use crate::util::automaton::{Automaton, LifeLike, MirroredTopology, SparseTopology, Topology};
+use crate::util::bench::Bencher;
+use crate::util::parser::{self, parse_all, ParseError};
+use crate::solution::Solution;
+use nom::{IResult, bytes::complete::{tag, take_while1}, character::complete::{alphanumeric1, char, digit1, newline}, combinator::{map_res, opt, recognize}, multi::{many0, separated_list1}, sequence::{pair, separated_pair, terminated, tuple}};
+
+/// The active cubes of the initial slice, which extends into every further dimension at coordinate zero.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Space {
+    active_set: HashSet<[i8; 2]>,
+}
+impl Space {
+    fn embed<const N: usize>(&self) -> HashSet<[i8; N]> {
+        assert!(N >= 2, "Expected atleast two dimensions");
+        self.active_set.iter().map(|&[x, y]| {
+            let mut position = [0; N];
+            position[0] = x;
+            position[1] = y;
+            position
+        }).collect()
+    }
+
+    /// The most cycles that can run before the active cubes could grow past the coordinates, as they spread by at
+    /// most one in every direction each cycle.
+    pub fn max_cycles(&self) -> usize {
+        self.active_set.iter()
+            .flat_map(|position| position.iter())
+            .map(|&coordinate| (i8::MAX as i16 - coordinate as i16).min(coordinate as i16 - i8::MIN as i16) as usize)
+            .fold(MAX_CYCLES, usize::min)
+    }
+}
+
+const BOOT_CYCLES: usize = 6;
+
+/// The most dimensions `active_counts` can simulate.
+pub const MAX_DIMENSIONS: usize = 8;
+
+/// The most cycles the further dimensions have room for, as they start at zero and grow by one every cycle. An
+/// initial slice may leave less room than that, see `Space::max_cycles`.
+pub const MAX_CYCLES: usize = i8::MAX as usize;
+
+/// The most rows, and cells per row, that a slice can have for its positions to fit the coordinates.
+const MAX_EXTENT: usize = i8::MAX as usize + 1;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Evolution {
+    /// Simulates every active cube.
+    Full,
+    /// Simulates only cubes with non-negative coordinates beyond the initial slice, which the rest mirror.
+    Mirrored,
+}
+
+fn population_per_cycle<T: Topology>(topology: T, initial: T::State, cycles: usize) -> Vec<usize> {
+    let mut automaton = Automaton::new(topology, LifeLike::conway(), initial);
+    let mut counts = vec![automaton.population()];
+    for _cycle in 0..cycles {
+        automaton.step();
+        counts.push(automaton.population());
+    }
+    counts
+}
+
+fn counts_in<const N: usize>(space: &Space, cycles: usize, evolution: Evolution) -> Vec<usize> {
+    assert!(cycles <= space.max_cycles(), "Cannot run more than {} cycles", space.max_cycles());
+    match evolution {
+        Evolution::Full => population_per_cycle(SparseTopology::new(), space.embed::<N>(), cycles),
+        Evolution::Mirrored => population_per_cycle(MirroredTopology::<N>::new(2), space.embed::<N>(), cycles),
+    }
+}
+
+/// Counts the active cubes after running `cycles` cycles in `N` dimensions.
+pub fn count_active<const N: usize>(space: &Space, cycles: usize, evolution: Evolution) -> usize {
+    *counts_in::<N>(space, cycles, evolution).last().unwrap()
+}
+
+/// The active cube counts of the initial state and after every cycle, in `dimensions` dimensions. Gives `None`
+/// for dimensions outside 2 to `MAX_DIMENSIONS`.
+pub fn active_counts(space: &Space, dimensions: usize, cycles: usize, evolution: Evolution) -> Option<Vec<usize>> {
+    match dimensions {
+        2 => Some(counts_in::<2>(space, cycles, evolution)),
+        3 => Some(counts_in::<3>(space, cycles, evolution)),
+        4 => Some(counts_in::<4>(space, cycles, evolution)),
+        5 => Some(counts_in::<5>(space, cycles, evolution)),
+        6 => Some(counts_in::<6>(space, cycles, evolution)),
+        7 => Some(counts_in::<7>(space, cycles, evolution)),
+        8 => Some(counts_in::<8>(space, cycles, evolution)),
+        _ => None,
+    }
+}
+
+/// The render for `dimensions` dimensions of the active cubes initially and after every cycle. Gives `None` for
+/// dimensions outside 2 to `MAX_DIMENSIONS`.
+pub fn render_cycles(space: &Space, dimensions: usize, cycles: usize) -> Option<Vec<String>> {
+    fn render_in<const N: usize>(space: &Space, cycles: usize) -> Vec<String> {
+        assert!(cycles <= space.max_cycles(), "Cannot run more than {} cycles", space.max_cycles());
+        let mut automaton = Automaton::new(SparseTopology::new(), LifeLike::conway(), space.embed::<N>());
+        let mut rendered = vec![render(automaton.state())];
+        for _cycle in 0..cycles {
+            automaton.step();
+            rendered.push(render(automaton.state()));
+        }
+        rendered
+    }
+
+    match dimensions {
+        2 => Some(render_in::<2>(space, cycles)),
+        3 => Some(render_in::<3>(space, cycles)),
+        4 => Some(render_in::<4>(space, cycles)),
+        5 => Some(render_in::<5>(space, cycles)),
+        6 => Some(render_in::<6>(space, cycles)),
+        7 => Some(render_in::<7>(space, cycles)),
+        8 => Some(render_in::<8>(space, cycles)),
+        _ => None,
+    }
+}
+
+fn dimension_name(dimension: usize) -> String {
+    match dimension {
+        0 => "x".to_owned(),
+        1 => "y".to_owned(),
+        2 => "z".to_owned(),
+        3 => "w".to_owned(),
+        dimension => format!("d{}", dimension),
+    }
+}
+
+/// Draws every x/y slice of the bounding box of the active cubes like the puzzle does, each headed by its
+/// coordinates in the further dimensions, such as `z=-1, w=0`.
+pub fn render<const N: usize>(active: &HashSet<[i8; N]>) -> String {
+    if active.is_empty() {
+        return String::new();
+    }
+    let min: Vec<i8> = (0..N).map(|dimension| active.iter().map(|position| position[dimension]).min().unwrap()).collect();
+    let max: Vec<i8> = (0..N).map(|dimension| active.iter().map(|position| position[dimension]).max().unwrap()).collect();
+
+    let mut slices = vec![];
+    let mut position = [0; N];
+    position[2..].copy_from_slice(&min[2..]);
+    loop {
+        let mut slice = (2..N)
+            .map(|dimension| format!("{}={}", dimension_name(dimension), position[dimension]))
+            .collect::<Vec<String>>()
+            .join(", ");
+        for y in min[1]..=max[1] {
+            if !slice.is_empty() {
+                slice.push('\n');
+            }
+            for x in min[0]..=max[0] {
+                position[0] = x;
+                position[1] = y;
+                slice.push(if active.contains(&position) { '#' } else { '.' });
+            }
+        }
+        slices.push(slice);
+
+        // Steps through the further coordinates with z changing fastest, like the puzzle orders its slices.
+        let mut dimension = 2;
+        loop {
+            if dimension == N {
+                return slices.join("\n\n");
+            }
+            if position[dimension] < max[dimension] {
+                position[dimension] += 1;
+                break;
+            }
+            position[dimension] = min[dimension];
+            dimension += 1;
+        }
+    }
+}
+
+type Slice<'a> = (Option<Vec<(&'a str, i8)>>, Vec<&'a str>);
+
+fn parse_coordinate(i: &str) -> IResult<&str, (&str, i8)> {
+    separated_pair(
+        alphanumeric1,
+        char('='),
+        map_res(recognize(pair(opt(char('-')), digit1)), |coordinate: &str| coordinate.parse())
+    )(i)
+}
+
+fn parse_slice(i: &str) -> IResult<&str, Slice<'_>> {
+    tuple((
+        opt(terminated(separated_list1(tag(", "), parse_coordinate), newline)),
+        separated_list1(newline, take_while1(|c| c == '#' || c == '.')),
+    ))(i)
+}
+
+/// Moves the active cubes in x and y so that their bounding box starts at zero, which is where `parse_slices` puts
+/// the top left of a drawing. The further coordinates stay as they are.
+pub fn normalise<const N: usize>(active: &HashSet<[i8; N]>) -> HashSet<[i8; N]> {
+    let min_x = active.iter().map(|position| position[0]).min().unwrap_or(0);
+    let min_y = active.iter().map(|position| position[1]).min().unwrap_or(0);
+    active.iter().map(|&position| {
+        let mut position = position;
+        position[0] -= min_x;
+        position[1] -= min_y;
+        position
+    }).collect()
+}
+
+/// Reads slices drawn like `render` does, positioning x and y relative to the top left of the drawing. A drawing
+/// does not say where its top left was, so this undoes `render` up to that translation: parsing the render of
+/// `active` gives `normalise(active)`. Compare states with fixtures after normalising them.
+pub fn parse_slices<const N: usize>(input: &str) -> Result<HashSet<[i8; N]>, ParseError> {
+    let expected: Vec<String> = (2..N).map(dimension_name).collect();
+    let parse_positioned_slice = map_res(parse_slice, |(coordinates, rows)| -> Result<Vec<[i8; N]>, String> {
+        let coordinates = coordinates.unwrap_or_default();
+        if rows.len() > MAX_EXTENT || rows.iter().any(|row| row.len() > MAX_EXTENT) {
+            return Err(format!("expected at most {} rows of {} cells", MAX_EXTENT, MAX_EXTENT));
+        }
+        if coordinates.iter().map(|&(name, _)| name).ne(expected.iter().map(|name| name.as_str())) {
+            return Err(format!("expected coordinates {}", expected.join(", ")));
+        }
+
+        let mut position = [0; N];
+        for (dimension, &(_, coordinate)) in coordinates.iter().enumerate() {
+            position[dimension + 2] = coordinate;
+        }
+        Ok(rows.iter().enumerate().flat_map(|(y, row)| {
+            row.bytes().enumerate().filter(|&(_, c)| c == b'#').map(move |(x, _)| {
+                let mut position = position;
+                position[0] = x as i8;
+                position[1] = y as i8;
+                position
+            })
+        }).collect())
+    });
+
+    let slices = parse_all(input, terminated(separated_list1(pair(newline, newline), parse_positioned_slice), many0(newline)))?;
+    Ok(slices.into_iter().flatten().collect())
+}
+
+pub struct Day17;
+impl Solution for Day17 {
+    type Input<'a> = Space;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Space, ParseError> {
+        let rows = parser::parse_grid_rows(input.as_bytes(), |c| c == b'#' || c == b'.')?;
+        if rows.len() > MAX_EXTENT {
+            return Err(ParseError::at(input.as_bytes(), rows[MAX_EXTENT], &format!("expected at most {} rows", MAX_EXTENT)));
+        }
+        if rows[0].len() > MAX_EXTENT {
+            return Err(ParseError::at(input.as_bytes(), &rows[0][MAX_EXTENT..], &format!("expected at most {} cells per row", MAX_EXTENT)));
+        }
+
+        let mut active_set = HashSet::new();
+        rows.iter().enumerate().for_each(|(y, line)| {
+            line.iter().enumerate().for_each(|(x, &char)| {
+                if char == b'#' {
+                    active_set.insert([x as i8 - 1, y as i8 - 1]);
+                }
+            });
+        });
+
+        Ok(Space { active_set })
+    }
+
+    fn part1(input: &Space) -> usize {
+        count_active::<3>(input, BOOT_CYCLES, Evolution::Mirrored)
+    }
+
+    fn part2(input: &Space) -> usize {
+        count_active::<4>(input, BOOT_CYCLES, Evolution::Mirrored)
+    }
+
+    fn bench_variants(input: &str, bencher: &mut Bencher) {
+        if let Ok(space) = Day17::parse(input) {
+            bencher.run("part 2 - full evolution", || count_active::<4>(&space, BOOT_CYCLES, Evolution::Full));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = 
+".#.
+..#
+###";
+
+    #[test]
+    fn parse_example() {
+        assert_eq!(Day17::parse(EXAMPLE_INPUT).unwrap(), Space {
+            active_set: vec![
+                [0, -1],
+                [1, 0],
+                [-1, 1],
+                [0, 1],
+                [1, 1],
+            ].into_iter().collect(),
+        });
+    }
+    
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day17::part1(&Day17::parse(EXAMPLE_INPUT).unwrap()), 112);
+    }
+    
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day17::part2(&Day17::parse(EXAMPLE_INPUT).unwrap()), 848);
+    }
+
+    #[test]
+    fn evolutions_agree_on_example() {
+        let space = Day17::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(count_active::<3>(&space, BOOT_CYCLES, Evolution::Full), 112);
+        assert_eq!(count_active::<4>(&space, BOOT_CYCLES, Evolution::Full), 848);
+        assert_eq!(count_active::<5>(&space, 3, Evolution::Mirrored), count_active::<5>(&space, 3, Evolution::Full));
+    }
+
+    const EXAMPLE_AFTER_ONE_CYCLE: &str =
+"z=-1
+#..
+..#
+.#.
+
+z=0
+#.#
+.##
+.#.
+
+z=1
+#..
+..#
+.#.";
+
+    #[test]
+    fn will_render_like_puzzle() {
+        let rendered = render_cycles(&Day17::parse(EXAMPLE_INPUT).unwrap(), 3, 1).unwrap();
+        assert_eq!(rendered[0], "z=0\n.#.\n..#\n###");
+        assert_eq!(rendered[1], EXAMPLE_AFTER_ONE_CYCLE);
+
+        let rendered = render_cycles(&Day17::parse(EXAMPLE_INPUT).unwrap(), 4, 1).unwrap();
+        assert!(rendered[1].starts_with("z=-1, w=-1\n#..\n..#\n.#.\n\nz=0, w=-1\n"));
+    }
+
+    #[test]
+    fn will_parse_rendered_slices() {
+        let active = parse_slices::<3>(EXAMPLE_AFTER_ONE_CYCLE).unwrap();
+        assert_eq!(active.len(), 11);
+        assert!(active.contains(&[0, 0, -1]));
+        assert_eq!(render(&active), EXAMPLE_AFTER_ONE_CYCLE);
+        assert_eq!(render(&parse_slices::<2>(EXAMPLE_INPUT).unwrap()), EXAMPLE_INPUT);
+
+        let error = parse_slices::<4>(EXAMPLE_AFTER_ONE_CYCLE).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+        assert!(parse_slices::<3>("z=0\n.#x").is_err());
+    }
+
+    #[test]
+    fn will_keep_coordinates_in_range() {
+        assert_eq!(Day17::parse(EXAMPLE_INPUT).unwrap().max_cycles(), MAX_CYCLES - 1);
+
+        let mut wide = ".".repeat(MAX_EXTENT - 10);
+        wide.push_str(".#.\n");
+        wide.push_str(&".".repeat(MAX_EXTENT - 10));
+        wide.push_str("..#");
+        let space = Day17::parse(&wide).unwrap();
+        assert_eq!(space.max_cycles(), 8);
+        assert_eq!(active_counts(&space, 2, 8, Evolution::Full), Some(vec![2, 0, 0, 0, 0, 0, 0, 0, 0]));
+
+        let error = Day17::parse(&format!("{}\n{}", ".".repeat(MAX_EXTENT + 1), ".".repeat(MAX_EXTENT + 1))).unwrap_err();
+        assert_eq!((error.line, error.column), (1, MAX_EXTENT + 1));
+        let error = Day17::parse(&"#\n".repeat(MAX_EXTENT + 1)).unwrap_err();
+        assert_eq!((error.line, error.column), (MAX_EXTENT + 1, 1));
+        assert!(parse_slices::<2>(&".".repeat(MAX_EXTENT + 1)).is_err());
+    }
+
+    #[test]
+    fn will_step_rendered_fixtures() {
+        let space = Day17::parse(EXAMPLE_INPUT).unwrap();
+        let initial = parse_slices::<3>(&format!("z=0\n{}", EXAMPLE_INPUT)).unwrap();
+        let mut automaton = Automaton::new(SparseTopology::new(), LifeLike::conway(), space.embed::<3>());
+        assert_ne!(automaton.state(), &initial);
+        assert_eq!(normalise(automaton.state()), initial);
+        automaton.step();
+        assert_eq!(normalise(automaton.state()), parse_slices::<3>(EXAMPLE_AFTER_ONE_CYCLE).unwrap());
+
+        let rendered = render_cycles(&space, 4, 2).unwrap();
+        let mut automaton = Automaton::new(SparseTopology::new(), LifeLike::conway(), parse_slices::<4>(&rendered[1]).unwrap());
+        automaton.step();
+        assert_eq!(normalise(automaton.state()), parse_slices::<4>(&rendered[2]).unwrap());
+    }
+
+    #[test]
+    fn will_count_every_cycle() {
+        let space = Day17::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(active_counts(&space, 3, 3, Evolution::Mirrored), Some(vec![5, 11, 21, 38]));
+        assert_eq!(active_counts(&space, 4, 2, Evolution::Full), Some(vec![5, 29, 60]));
+        assert_eq!(active_counts(&space, 1, 6, Evolution::Full), None);
+        assert_eq!(active_counts(&space, MAX_DIMENSIONS + 1, 6, Evolution::Full), None);
+    }
+}
\ No newline at end of file
@@ -1,7 +1,5 @@
-#![feature(test)]
-
-use std::fs;
-use aoc_2020_rust::util::{bench, parser::parse_decimal_u64};
+use crate::util::parser::{parse_all, parse_decimal_u64, ParseError};
+use crate::solution::Solution;
 use nom::IResult;
 use nom::sequence::*;
 use nom::character::complete::*;
@@ -48,12 +46,6 @@ fn parse_expression1(i: &[u8]) -> IResult<&[u8], u64> {
     )(i)
 }
 
-fn part1(input: &str) -> u64 {
-    input.lines().map(|line| {
-        parse_expression1(line.as_bytes()).unwrap().1
-    }).sum()
-}
-
 fn parse_expression2(i: &[u8]) -> IResult<&[u8], u64> {
     fn parse_inner_expression(i: &[u8]) -> IResult<&[u8], u64> {
         fn parse_single_expression(i: &[u8]) -> IResult<&[u8], u64> {
@@ -86,20 +78,28 @@ fn parse_expression2(i: &[u8]) -> IResult<&[u8], u64> {
     })(i)
 }
 
-fn part2(input: &str) -> u64 {
-    input.lines().map(|line| {
-        parse_expression2(line.as_bytes()).unwrap().1
-    }).sum()
-}
+pub struct Day18;
+impl Solution for Day18 {
+    type Input<'a> = &'a str;
+    type Output1 = u64;
+    type Output2 = u64;
 
-fn main() {
-    let input = fs::read_to_string("./src/day18/input.txt").expect("Unable to read input file");
+    fn parse(input: &str) -> Result<&str, ParseError> {
+        parse_all(input.as_bytes(), many1(terminated(parse_expression1, opt(newline))))?;
+        Ok(input)
+    }
 
-    let part1 = bench::time("part 1", || part1(&input));
-    let part2 = bench::time("part 2", || part2(&input));
+    fn part1(input: &&str) -> u64 {
+        input.lines().map(|line| {
+            parse_expression1(line.as_bytes()).unwrap().1
+        }).sum()
+    }
 
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
+    fn part2(input: &&str) -> u64 {
+        input.lines().map(|line| {
+            parse_expression2(line.as_bytes()).unwrap().1
+        }).sum()
+    }
 }
 
 #[cfg(test)]
@@ -1,13 +1,14 @@
-use std::{collections::HashMap, fs, iter::{empty, once}};
-use aoc_2020_rust::util::bench;
-use nom::{IResult, branch::alt, bytes::complete::tag, character::complete::{alpha1, char, digit1}, combinator::{all_consuming, map}, multi::separated_list1, sequence::delimited};
+use std::{collections::HashMap, iter::{empty, once}, num::ParseIntError};
+use crate::util::parser::{parse_all, ParseError};
+use crate::solution::Solution;
+use nom::{IResult, branch::alt, bytes::complete::tag, character::complete::{alpha1, char, digit1, newline}, combinator::{map, map_res, opt}, multi::{many1, separated_list1}, sequence::{delimited, separated_pair, terminated}};
 
 type BoxedIterator<'a, 'b> = Box<dyn Iterator<Item = &'a str> + 'b>;
 
 type Rules = HashMap<u64, Parser>;
 
-#[derive(Clone)]
-enum Parser {
+#[derive(Debug, Clone)]
+pub enum Parser {
     And {
         definitions: Vec<Parser>,
     },
@@ -57,69 +58,60 @@ impl Parser {
     }
 }
 
-type Input<'a> = (Rules, Vec<&'a str>);
+type PreparedInput<'a> = (Rules, Vec<&'a str>);
 
-fn parse_rule(i: &str) -> Parser {
-    fn inner_parse_rule(i: &str) -> IResult<&str, Parser> {
+fn parse_rule(i: &str) -> IResult<&str, Parser> {
+    map(separated_list1(
+        tag(" | "),
         map(separated_list1(
-            tag(" | "),
-            map(separated_list1(
-                char(' '),
-                alt((
-                    map(
-                        delimited(char('"'), alpha1, char('"')),
-                        |char: &str| -> Parser {
-                            Parser::String { 
-                                string: char.to_owned()
-                            }
+            char(' '),
+            alt((
+                map(
+                    delimited(char('"'), alpha1, char('"')),
+                    |char: &str| -> Parser {
+                        Parser::String { 
+                            string: char.to_owned()
                         }
-                    ),
-                    map(digit1, |a: &str| -> Parser {
-                        Parser::Reference {
-                            index: a.parse().unwrap(),
-                        }
-                    }),
-                ))
-            ), |parts| -> Parser {
-                if parts.len() == 1 {
-                    return parts.into_iter().next().unwrap().into();
-                }
-                Parser::And {
-                    definitions: parts,
-                }
-            })
+                    }
+                ),
+                map_res(digit1, |a: &str| -> Result<Parser, ParseIntError> {
+                    Ok(Parser::Reference {
+                        index: a.parse()?,
+                    })
+                }),
+            ))
         ), |parts| -> Parser {
             if parts.len() == 1 {
                 return parts.into_iter().next().unwrap().into();
             }
-            Parser::Or {
+            Parser::And {
                 definitions: parts,
             }
-        })(i)
-    }
-    all_consuming(inner_parse_rule)(i).unwrap().1
+        })
+    ), |parts| -> Parser {
+        if parts.len() == 1 {
+            return parts.into_iter().next().unwrap().into();
+        }
+        Parser::Or {
+            definitions: parts,
+        }
+    })(i)
 }
 
-fn prepare_input<'a>(input: &'a str) -> Input<'a> {
-    let sections: Vec<&str> = input.split("\n\n").collect();
-    assert!(sections.len() == 2);
-
-    let rules = sections[0].lines().fold(HashMap::new(), |mut rules, line| {
-        let mut line_split = line.split(": ");
-        let index = line_split.next().unwrap().parse::<u64>().unwrap();
-        let unparsed_rule = line_split.next().unwrap();
-        let rule = parse_rule(unparsed_rule);
-        
-        rules.insert(index, rule);
-        rules
-    });
+fn parse_rule_line(i: &str) -> IResult<&str, (u64, Parser)> {
+    separated_pair(
+        map_res(digit1, |index: &str| index.parse()),
+        tag(": "),
+        parse_rule
+    )(i)
+}
 
-    let messages = sections[1].lines().collect();
-    
-    (
-        rules,
-        messages,
-    )
+fn parse_input(i: &str) -> IResult<&str, PreparedInput<'_>> {
+    separated_pair(
+        map(many1(terminated(parse_rule_line, newline)), |rules| rules.into_iter().collect()),
+        newline,
+        many1(terminated(alpha1, opt(newline))),
+    )(i)
 }
 
 fn run(rules: &Rules, messages: &Vec<&str>) -> u64 {
@@ -132,58 +124,58 @@ fn run(rules: &Rules, messages: &Vec<&str>) -> u64 {
     }).count() as u64
 }
 
-fn part1(input: &Input) -> u64 {
-    let rules = &input.0;
-    let messages = &input.1;
+pub struct Day19;
+impl Solution for Day19 {
+    type Input<'a> = PreparedInput<'a>;
+    type Output1 = u64;
+    type Output2 = u64;
 
-    run(rules, messages)
-}
-
-fn part2(input: &Input) -> u64 {
-    let rules = &input.0;
-    let messages = &input.1;
+    fn parse(input: &str) -> Result<PreparedInput<'_>, ParseError> {
+        parse_all(input, parse_input)
+    }
 
-    let mut updated_rules = (*rules).clone();
-    updated_rules.insert(8, Parser::And{
-        definitions: vec![
-            Parser::Reference { index: 42 },
-            Parser::Or {
-                definitions: vec![
-                    Parser::Reference { index: 8 },
-                    Parser::String { string: "".to_owned() },
-                ],
-            },
-        ]
-    });
-    updated_rules.insert(11, Parser::And{
-        definitions: vec![
-            Parser::Reference { index: 42 },
-            Parser::Or {
-                definitions: vec![
-                    Parser::And{
-                        definitions: vec![
-                            Parser::Reference { index: 11 },
-                            Parser::Reference { index: 31 },
-                        ],
-                    },
-                    Parser::Reference { index: 31 },
-                ],
-            },
-        ]
-    });
+    fn part1(input: &PreparedInput) -> u64 {
+        let rules = &input.0;
+        let messages = &input.1;
 
-    run(&updated_rules, messages)
-}
+        run(rules, messages)
+    }
 
-fn main() {
-    let input = fs::read_to_string("./src/day19/input.txt").expect("Unable to read input file");
+    fn part2(input: &PreparedInput) -> u64 {
+        let rules = &input.0;
+        let messages = &input.1;
 
-    let prepared_input = bench::time("input preparation", || prepare_input(&input));
-    let part1 = bench::time("part 1", || part1(&prepared_input));
-    let part2 = bench::time("part 2", || part2(&prepared_input));
+        let mut updated_rules = (*rules).clone();
+        updated_rules.insert(8, Parser::And{
+            definitions: vec![
+                Parser::Reference { index: 42 },
+                Parser::Or {
+                    definitions: vec![
+                        Parser::Reference { index: 8 },
+                        Parser::String { string: "".to_owned() },
+                    ],
+                },
+            ]
+        });
+        updated_rules.insert(11, Parser::And{
+            definitions: vec![
+                Parser::Reference { index: 42 },
+                Parser::Or {
+                    definitions: vec![
+                        Parser::And{
+                            definitions: vec![
+                                Parser::Reference { index: 11 },
+                                Parser::Reference { index: 31 },
+                            ],
+                        },
+                        Parser::Reference { index: 31 },
+                    ],
+                },
+            ]
+        });
 
-    println!("Part 1: {}", part1);
-    println!("Part 2: {}", part2);
+        run(&updated_rules, messages)
+    }
 }
 
 #[cfg(test)]
@@ -217,7 +209,7 @@ mod tests {
 aab
 aba";
 
-        assert_eq!(part1(&prepare_input(EXAMPLE_INPUT)), 2);
+        assert_eq!(Day19::part1(&Day19::parse(EXAMPLE_INPUT).unwrap()), 2);
     }
     #[test]
     fn part1_example2() {
@@ -235,7 +227,7 @@ abbbab
 aaabbb
 aaaabbb";
     
-        assert_eq!(part1(&prepare_input(EXAMPLE_INPUT)), 2);
+        assert_eq!(Day19::part1(&Day19::parse(EXAMPLE_INPUT).unwrap()), 2);
     }
 
     #[test]
@@ -289,7 +281,13 @@ aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
 babaaabbbaaabaababbaabababaaab
 aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
     
-        assert_eq!(part1(&prepare_input(EXAMPLE_INPUT)), 3);
-        assert_eq!(part2(&prepare_input(EXAMPLE_INPUT)), 12);
+        assert_eq!(Day19::part1(&Day19::parse(EXAMPLE_INPUT).unwrap()), 3);
+        assert_eq!(Day19::part2(&Day19::parse(EXAMPLE_INPUT).unwrap()), 12);
+    }
+
+    #[test]
+    fn parse_error_is_located() {
+        let error = Day19::parse("0: 1 2\n1: \"a\"\n2: 1 x\n\naab").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
     }
 }
\ No newline at end of file
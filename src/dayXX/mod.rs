@@ -0,0 +1,46 @@
+use crate::util::parser::ParseError;
+use crate::solution::Solution;
+
+type PreparedInput = String;
+
+pub struct DayXX;
+impl Solution for DayXX {
+    type Input<'a> = PreparedInput;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<PreparedInput, ParseError> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &PreparedInput) -> u32 {
+        0
+    }
+
+    fn part2(input: &PreparedInput) -> u32 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = 
+"";
+
+    #[test]
+    fn parse_example() {
+        assert_eq!(DayXX::parse(EXAMPLE_INPUT).unwrap(), EXAMPLE_INPUT);
+    }
+    
+    #[test]
+    fn part1_example() {
+        assert_eq!(DayXX::part1(&DayXX::parse(EXAMPLE_INPUT).unwrap()), 0);
+    }
+    
+    #[test]
+    fn part2_example() {
+        assert_eq!(DayXX::part2(&DayXX::parse(EXAMPLE_INPUT).unwrap()), 0);
+    }
+}
\ No newline at end of file
@@ -1 +1,23 @@
-pub mod util;
\ No newline at end of file
+pub mod util;
+pub mod solution;
+pub mod runner;
+pub mod answers;
+pub mod report;
+pub mod vm;
+
+pub mod day03;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
@@ -0,0 +1,883 @@
+use std::{env, fs, io::{self, Write}, process, time::{SystemTime, UNIX_EPOCH}};
+use aoc_2020_rust::day07::{self, Day07};
+use aoc_2020_rust::day08::Day08;
+use aoc_2020_rust::day17::{self, Day17, Evolution};
+use aoc_2020_rust::runner::{self, Day, InputSource, Part};
+use aoc_2020_rust::solution::Solution;
+use aoc_2020_rust::answers::{Answers, Verdict};
+use aoc_2020_rust::report::{self, Format, Record};
+use aoc_2020_rust::util::bench::{self, Bencher, Iterations, Measurement};
+use aoc_2020_rust::util::random::Random;
+use aoc_2020_rust::vm::{assemble, debug::{self, Debugger}, disassemble, dot, generate, Machine, Register};
+
+const USAGE: &str = "Usage:
+    aoc run <day|all> [--part <1|2>] [--input <path|->] [--time] [--quiet]
+    aoc verify [day|all] [--answers <path>]
+    aoc bench <day|all> [--iterations <n>] [--warmup <n>] [--input <path|->] [--format <text|json|csv>] [--output <path>]
+    aoc compare <baseline> <current> [--threshold <percent>]
+    aoc cubes [--dimensions <n>] [--cycles <n>] [--every-cycle] [--full] [--render] [--input <path|->]
+    aoc console [--trace | --debug | --disassemble | --dot] [--assembly] [--values <n,n,...>] [--no-loop-detection] [--max-steps <n>] [--input <path|->]
+    aoc generate [--size <n>] [--seed <n>] [--output <path>]
+    aoc bags (--contains <colour> | --inside <colour> | --path <outer> <inner>) [--input <path|->]";
+
+#[derive(Debug, PartialEq)]
+struct RunOptions {
+    days: Vec<u32>,
+    parts: Vec<Part>,
+    input: InputSource,
+    /// Report how long each step took on stderr.
+    time: bool,
+    /// Print only the answers, one per line.
+    quiet: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct VerifyOptions {
+    days: Vec<u32>,
+    answers: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchOptions {
+    days: Vec<u32>,
+    input: InputSource,
+    config: bench::Config,
+    /// Machine-readable output written after every day is measured, or plain text as measurements come in.
+    format: Option<Format>,
+    output: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct CompareOptions {
+    baseline: String,
+    current: String,
+    /// Relative slowdown of the median beyond which a measurement counts as a regression.
+    threshold: f64,
+}
+
+/// Explores day 17's Conway cubes beyond the two puzzle answers.
+#[derive(Debug, PartialEq)]
+struct CubesOptions {
+    input: InputSource,
+    dimensions: usize,
+    cycles: usize,
+    /// Print the count after every cycle rather than only the last.
+    every_cycle: bool,
+    evolution: Evolution,
+    /// Draw the slices of every printed cycle.
+    render: bool,
+}
+
+/// How to execute a day 8 program on the console.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ConsoleMode {
+    /// Only report why the program halted.
+    Run,
+    /// Print every executed instruction.
+    Trace,
+    /// Step through the program with commands read from stdin.
+    Debug,
+    /// List the program with labelled jump targets instead of running it.
+    Disassemble,
+    /// Write the control-flow graph in Graphviz DOT instead of running the program.
+    Dot,
+}
+
+#[derive(Debug, PartialEq)]
+struct ConsoleOptions {
+    input: InputSource,
+    mode: ConsoleMode,
+    /// Read the program as assembler source with labels and comments.
+    assembly: bool,
+    /// The values `in` instructions read, in order.
+    values: Vec<i32>,
+    /// Stop the first time an instruction would run again, like day 8 expects.
+    loop_detection: bool,
+    /// Stop after this many instructions.
+    max_steps: Option<usize>,
+}
+
+/// The step budget when loop detection is off and no budget is given, so looping programs still stop.
+const DEFAULT_MAX_STEPS: usize = 10_000_000;
+
+/// Writes a random day 8 program with a known answer.
+#[derive(Debug, PartialEq)]
+struct GenerateOptions {
+    size: usize,
+    /// Taken from the clock when not given, and reported so the program can be generated again.
+    seed: Option<u64>,
+    output: Option<String>,
+}
+
+/// A question about day 7's bag rules, naming colours such as `shiny gold`.
+#[derive(Debug, PartialEq)]
+enum BagQuery {
+    /// Which colours can eventually hold this one.
+    Contains(String),
+    /// How many bags this colour holds, in total and per colour.
+    Inside(String),
+    /// How the outer colour ends up holding the inner one.
+    Path(String, String),
+}
+
+#[derive(Debug, PartialEq)]
+struct BagsOptions {
+    input: InputSource,
+    query: BagQuery,
+}
+
+fn parse_days(arg: &str) -> Result<Vec<u32>, String> {
+    match arg {
+        "all" => Ok(runner::DAYS.iter().map(|entry| entry.day).collect()),
+        day => Ok(vec![day.parse().map_err(|_| format!("Invalid day '{}'", day))?]),
+    }
+}
+
+fn find_days(days: &[u32]) -> Result<Vec<&'static Day>, String> {
+    days.iter().map(|&day| {
+        runner::find(day).ok_or(format!("Day {} is not registered", day))
+    }).collect()
+}
+
+fn parse_run_options(args: &[String]) -> Result<RunOptions, String> {
+    let mut days = None;
+    let mut parts = runner::ALL_PARTS.to_vec();
+    let mut input = InputSource::Default;
+    let mut time = false;
+    let mut quiet = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--time" => time = true,
+            "--quiet" => quiet = true,
+            "--part" => {
+                parts = match args.next().map(|part| part.as_str()) {
+                    Some("1") => vec![Part::One],
+                    Some("2") => vec![Part::Two],
+                    other => return Err(format!("Invalid part {:?}", other)),
+                };
+            }
+            "--input" => {
+                input = InputSource::from_arg(args.next().ok_or("Missing input path")?);
+            }
+            day if days.is_some() => return Err(format!("Unexpected second day '{}'", day)),
+            day => days = Some(parse_days(day)?),
+        }
+    }
+
+    let days: Vec<u32> = days.ok_or("Missing day")?;
+    if days.len() > 1 && input != InputSource::Default {
+        return Err("An explicit input can only be used with a single day".to_owned());
+    }
+
+    Ok(RunOptions {
+        days,
+        parts,
+        input,
+        time,
+        quiet,
+    })
+}
+
+fn parse_verify_options(args: &[String]) -> Result<VerifyOptions, String> {
+    let mut days = None;
+    let mut answers = Answers::default_path();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = args.next().ok_or("Missing answers path")?.to_owned(),
+            day if days.is_some() => return Err(format!("Unexpected second day '{}'", day)),
+            day => days = Some(parse_days(day)?),
+        }
+    }
+
+    Ok(VerifyOptions {
+        days: match days {
+            Some(days) => days,
+            None => parse_days("all")?,
+        },
+        answers,
+    })
+}
+
+fn parse_bench_options(args: &[String]) -> Result<BenchOptions, String> {
+    let mut days = None;
+    let mut input = InputSource::Default;
+    let mut config = bench::Config::auto();
+    let mut format = None;
+    let mut output = None;
+
+    let parse_count = |count: Option<&String>| -> Result<u32, String> {
+        let count = count.ok_or("Missing count")?;
+        count.parse().map_err(|_| format!("Invalid count '{}'", count))
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" => match parse_count(args.next())? {
+                0 => return Err("Iterations must be at least 1".to_owned()),
+                iterations => config.iterations = Iterations::Fixed(iterations),
+            },
+            "--warmup" => config.warmup = parse_count(args.next())?,
+            "--input" => input = InputSource::from_arg(args.next().ok_or("Missing input path")?),
+            "--format" => {
+                format = match args.next().map(|format| format.as_str()) {
+                    Some("text") => None,
+                    Some(other) => Some(Format::from_arg(other).ok_or(format!("Invalid format '{}'", other))?),
+                    None => return Err("Missing format".to_owned()),
+                };
+            }
+            "--output" => output = Some(args.next().ok_or("Missing output path")?.to_owned()),
+            day if days.is_some() => return Err(format!("Unexpected second day '{}'", day)),
+            day => days = Some(parse_days(day)?),
+        }
+    }
+
+    let days: Vec<u32> = days.ok_or("Missing day")?;
+    if days.len() > 1 && input != InputSource::Default {
+        return Err("An explicit input can only be used with a single day".to_owned());
+    }
+    if output.is_some() && format.is_none() {
+        return Err("An output path requires --format json or csv".to_owned());
+    }
+
+    Ok(BenchOptions {
+        days,
+        input,
+        config,
+        format,
+        output,
+    })
+}
+
+fn parse_compare_options(args: &[String]) -> Result<CompareOptions, String> {
+    let mut paths = vec![];
+    let mut threshold = 10.0;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threshold" => {
+                let percent = args.next().ok_or("Missing threshold")?;
+                threshold = percent.parse().map_err(|_| format!("Invalid threshold '{}'", percent))?;
+            }
+            path => paths.push(path.to_owned()),
+        }
+    }
+
+    match paths.as_slice() {
+        [baseline, current] => Ok(CompareOptions {
+            baseline: baseline.to_owned(),
+            current: current.to_owned(),
+            threshold: threshold / 100.0,
+        }),
+        _ => Err("Expected a baseline and a current results file".to_owned()),
+    }
+}
+
+fn parse_cubes_options(args: &[String]) -> Result<CubesOptions, String> {
+    let mut options = CubesOptions {
+        input: InputSource::Default,
+        dimensions: 3,
+        cycles: 6,
+        every_cycle: false,
+        evolution: Evolution::Mirrored,
+        render: false,
+    };
+
+    let parse_count = |count: Option<&String>| -> Result<usize, String> {
+        let count = count.ok_or("Missing count")?;
+        count.parse().map_err(|_| format!("Invalid count '{}'", count))
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dimensions" => options.dimensions = parse_count(args.next())?,
+            "--cycles" => options.cycles = parse_count(args.next())?,
+            "--every-cycle" => options.every_cycle = true,
+            "--full" => options.evolution = Evolution::Full,
+            "--render" => options.render = true,
+            "--input" => options.input = InputSource::from_arg(args.next().ok_or("Missing input path")?),
+            other => return Err(format!("Unknown argument '{}'", other)),
+        }
+    }
+
+    if options.dimensions < 2 || options.dimensions > day17::MAX_DIMENSIONS {
+        return Err(format!("Dimensions must be between 2 and {}", day17::MAX_DIMENSIONS));
+    }
+    if options.cycles > day17::MAX_CYCLES {
+        return Err(format!("Cycles must be at most {}", day17::MAX_CYCLES));
+    }
+    Ok(options)
+}
+
+fn parse_console_options(args: &[String]) -> Result<ConsoleOptions, String> {
+    let mut options = ConsoleOptions {
+        input: InputSource::Default,
+        mode: ConsoleMode::Run,
+        assembly: false,
+        values: vec![],
+        loop_detection: true,
+        max_steps: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trace" => options.mode = ConsoleMode::Trace,
+            "--debug" => options.mode = ConsoleMode::Debug,
+            "--disassemble" => options.mode = ConsoleMode::Disassemble,
+            "--dot" => options.mode = ConsoleMode::Dot,
+            "--assembly" => options.assembly = true,
+            "--values" => {
+                let values = args.next().ok_or("Missing input values")?;
+                options.values = values.split(',')
+                    .map(|value| value.trim().parse().map_err(|_| format!("Invalid input value '{}'", value)))
+                    .collect::<Result<_, _>>()?;
+            }
+            "--no-loop-detection" => options.loop_detection = false,
+            "--max-steps" => {
+                let max_steps = args.next().ok_or("Missing step count")?;
+                options.max_steps = Some(max_steps.parse().map_err(|_| format!("Invalid step count '{}'", max_steps))?);
+            }
+            "--input" => options.input = InputSource::from_arg(args.next().ok_or("Missing input path")?),
+            other => return Err(format!("Unknown argument '{}'", other)),
+        }
+    }
+
+    if options.mode == ConsoleMode::Debug && options.input == InputSource::Stdin {
+        return Err("The debugger reads commands from stdin, so the program must come from a file".to_owned());
+    }
+    Ok(options)
+}
+
+fn parse_generate_options(args: &[String]) -> Result<GenerateOptions, String> {
+    let mut options = GenerateOptions {
+        size: 1000,
+        seed: None,
+        output: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                let size = args.next().ok_or("Missing size")?;
+                options.size = size.parse().ok().filter(|&size| size > 0).ok_or(format!("Invalid size '{}'", size))?;
+            }
+            "--seed" => {
+                let seed = args.next().ok_or("Missing seed")?;
+                options.seed = Some(seed.parse().map_err(|_| format!("Invalid seed '{}'", seed))?);
+            }
+            "--output" => options.output = Some(args.next().ok_or("Missing output path")?.to_owned()),
+            other => return Err(format!("Unknown argument '{}'", other)),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_bags_options(args: &[String]) -> Result<BagsOptions, String> {
+    let mut input = InputSource::Default;
+    let mut query = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut colour = || args.next().map(|colour| colour.to_owned()).ok_or("Missing colour");
+        let next = match arg.as_str() {
+            "--contains" => BagQuery::Contains(colour()?),
+            "--inside" => BagQuery::Inside(colour()?),
+            "--path" => BagQuery::Path(colour()?, colour()?),
+            "--input" => {
+                input = InputSource::from_arg(args.next().ok_or("Missing input path")?);
+                continue;
+            }
+            other => return Err(format!("Unknown argument '{}'", other)),
+        };
+        if query.replace(next).is_some() {
+            return Err("Expected a single query".to_owned());
+        }
+    }
+
+    Ok(BagsOptions {
+        input,
+        query: query.ok_or("Expected --contains, --inside or --path")?,
+    })
+}
+
+/// Writes a line to stdout like `println!`, but gives an error instead of panicking when that fails.
+macro_rules! outln {
+    ($($arg:tt)*) => { writeln!(io::stdout().lock(), $($arg)*).map_err(write_failed) };
+}
+
+/// Describes a failed write to stdout. A closed pipe, as when the output goes into `head`, only means that nobody
+/// reads the rest, so that ends the program quietly instead.
+fn write_failed(error: io::Error) -> String {
+    if error.kind() == io::ErrorKind::BrokenPipe {
+        process::exit(0);
+    }
+    format!("Unable to write output: {}", error)
+}
+
+/// Prints every measurement as soon as it is taken.
+struct StdoutSink;
+impl bench::Sink for StdoutSink {
+    fn record(&mut self, measurement: &Measurement) {
+        if let Err(message) = outln!("{}", measurement) {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    }
+}
+
+fn read_input(entry: &Day, input: &InputSource) -> Result<String, String> {
+    input.read(entry)
+        .map_err(|error| format!("Unable to read input {}: {}", input.describe(entry), error))
+}
+
+fn solve(entry: &Day, input: &InputSource, parts: &[Part], timings: &mut dyn bench::Sink) -> Result<Vec<runner::Answer>, String> {
+    (entry.solve)(&read_input(entry, input)?, parts, timings)
+        .map_err(|error| format!("Unable to parse input {}: {}", input.describe(entry), error))
+}
+
+fn run(options: &RunOptions) -> Result<(), String> {
+    for entry in find_days(&options.days)? {
+        let timings: &mut dyn bench::Sink = if options.time {
+            eprintln!("Day {}", entry.day);
+            &mut bench::Stderr
+        } else {
+            &mut bench::Discard
+        };
+
+        for answer in solve(entry, &options.input, &options.parts, timings)? {
+            if options.quiet {
+                outln!("{}", answer.value)?;
+            } else {
+                outln!("Day {} part {}: {}", entry.day, answer.part, answer.value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn bench(options: &BenchOptions) -> Result<(), String> {
+    let mut records = vec![];
+    for entry in find_days(&options.days)? {
+        let contents = read_input(entry, &options.input)?;
+        let mut measurements: Vec<Measurement> = vec![];
+
+        let sink: &mut dyn bench::Sink = match options.format {
+            Some(_) => &mut measurements,
+            None => {
+                outln!("Day {}", entry.day)?;
+                &mut StdoutSink
+            }
+        };
+        (entry.bench)(&contents, &mut Bencher::new(options.config, sink))
+            .map_err(|error| format!("Unable to parse input {}: {}", options.input.describe(entry), error))?;
+
+        records.extend(measurements.iter().map(|measurement| Record::new(entry.day, measurement)));
+    }
+
+    if let Some(format) = options.format {
+        let written = format.write(&records);
+        match &options.output {
+            Some(path) => fs::write(path, written).map_err(|error| format!("Unable to write {}: {}", path, error))?,
+            None => io::stdout().lock().write_all(written.as_bytes()).map_err(write_failed)?,
+        }
+    }
+    Ok(())
+}
+
+fn read_records(path: &str) -> Result<Vec<Record>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| format!("Unable to read results {}: {}", path, error))?;
+    report::parse(&contents)
+        .map_err(|error| format!("Unable to parse results {}: {}", path, error))
+}
+
+fn compare(options: &CompareOptions) -> Result<(), String> {
+    let baseline = read_records(&options.baseline)?;
+    let current = read_records(&options.current)?;
+
+    let mut regressions = 0;
+    for comparison in report::compare(&baseline, &current) {
+        let regressed = comparison.is_regression(options.threshold);
+        outln!(
+            "{}: {:?} -> {:?} ({:+.1}%){}",
+            comparison.current, comparison.baseline.median, comparison.current.median,
+            comparison.change() * 100.0, if regressed { " REGRESSION" } else { "" }
+        )?;
+        if regressed {
+            regressions += 1;
+        }
+    }
+
+    if regressions > 0 {
+        return Err(format!("{} measurements regressed by more than {}%", regressions, options.threshold * 100.0));
+    }
+    Ok(())
+}
+
+fn cubes(options: &CubesOptions) -> Result<(), String> {
+    let entry = runner::find(17).ok_or("Day 17 is not registered")?;
+    let space = Day17::parse(&read_input(entry, &options.input)?)
+        .map_err(|error| format!("Unable to parse input {}: {}", options.input.describe(entry), error))?;
+
+    if options.cycles > space.max_cycles() {
+        return Err(format!("The cubes of this input only have room for {} cycles", space.max_cycles()));
+    }
+
+    let unsupported = || format!("Unable to simulate {} dimensions", options.dimensions);
+    let counts = day17::active_counts(&space, options.dimensions, options.cycles, options.evolution).ok_or_else(unsupported)?;
+    let rendered = match options.render {
+        true => Some(day17::render_cycles(&space, options.dimensions, options.cycles).ok_or_else(unsupported)?),
+        false => None,
+    };
+
+    let first = if options.every_cycle { 0 } else { options.cycles };
+    for cycle in first..=options.cycles {
+        match (&rendered, options.every_cycle) {
+            (Some(rendered), _) => outln!("Cycle {}: {}\n\n{}\n", cycle, counts[cycle], rendered[cycle])?,
+            (None, true) => outln!("Cycle {}: {}", cycle, counts[cycle])?,
+            (None, false) => outln!("{}", counts[cycle])?,
+        }
+    }
+    Ok(())
+}
+
+fn console(options: &ConsoleOptions) -> Result<(), String> {
+    let entry = runner::find(8).ok_or("Day 8 is not registered")?;
+    let contents = read_input(entry, &options.input)?;
+    let program = if options.assembly { assemble::assemble(&contents) } else { Day08::parse(&contents) }
+        .map_err(|error| format!("Unable to parse input {}: {}", options.input.describe(entry), error))?;
+    let mut machine = Machine::new(&program).with_input(options.values.iter().copied());
+    let mut max_steps = options.max_steps;
+    if !options.loop_detection {
+        machine = machine.without_loop_detection();
+        max_steps = max_steps.or(Some(DEFAULT_MAX_STEPS));
+    }
+
+    let halt = match options.mode {
+        ConsoleMode::Run => match max_steps {
+            Some(max_steps) => machine.run_with_budget(max_steps),
+            None => machine.run(),
+        },
+        ConsoleMode::Trace => debug::trace(&mut machine, &mut io::stdout().lock(), max_steps)
+            .map_err(write_failed)?,
+        ConsoleMode::Debug => {
+            return Debugger::new(machine).with_max_steps(max_steps).repl(io::stdin().lock(), &mut io::stdout())
+                .map_err(write_failed);
+        }
+        ConsoleMode::Disassemble => {
+            outln!("{}", disassemble::disassemble(&program))?;
+            return Ok(());
+        }
+        ConsoleMode::Dot => {
+            outln!("{}", dot::control_flow_graph(&program))?;
+            return Ok(());
+        }
+    };
+    outln!("Halted: {}, acc {}", halt, machine.accumulator())?;
+    let registers: Vec<String> = machine.registers().iter().enumerate()
+        .map(|(register, value)| format!("{} {}", Register(register as u8), value))
+        .collect();
+    outln!("Registers: {}", registers.join(", "))?;
+    if !machine.output().is_empty() {
+        let output: Vec<String> = machine.output().iter().map(|value| value.to_string()).collect();
+        outln!("Output: {}", output.join(", "))?;
+    }
+    Ok(())
+}
+
+fn generate(options: &GenerateOptions) -> Result<(), String> {
+    let seed = options.seed.unwrap_or_else(|| {
+        SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64)
+    });
+    let generated = generate::generate(options.size, &mut Random::new(seed));
+
+    let written: String = generated.program.iter().map(|instruction| format!("{}\n", instruction)).collect();
+    match &options.output {
+        Some(path) => fs::write(path, written).map_err(|error| format!("Unable to write {}: {}", path, error))?,
+        None => io::stdout().lock().write_all(written.as_bytes()).map_err(write_failed)?,
+    }
+
+    eprintln!("Seed: {}", seed);
+    eprintln!("Part 1: {}", generated.loop_accumulator);
+    eprintln!("Part 2: {} (pc {} becomes {})", generated.repair.accumulator, generated.repair.pc, generated.repair.replacement);
+    Ok(())
+}
+
+fn bags(options: &BagsOptions) -> Result<(), String> {
+    let entry = runner::find(7).ok_or("Day 7 is not registered")?;
+    let contents = read_input(entry, &options.input)?;
+    let rules = Day07::parse(&contents)
+        .map_err(|error| format!("Unable to parse input {}: {}", options.input.describe(entry), error))?;
+
+    let find = |colour: &str| rules.id(colour.as_bytes()).ok_or(format!("Unknown bag colour '{}'", colour));
+    let name = |colour: &[u8]| String::from_utf8_lossy(colour).into_owned();
+
+    match &options.query {
+        BagQuery::Contains(colour) => {
+            let containers = day07::containers(&rules, find(colour)?);
+            outln!("{} colours can hold {}", containers.len(), colour)?;
+            for container in containers {
+                outln!("{}", name(container))?;
+            }
+        }
+        BagQuery::Inside(colour) => {
+            let id = find(colour)?;
+            let cycle = |_| format!("A {} bag can end up inside itself", colour);
+            outln!("{} holds {} bags", colour, day07::count_inside(&rules, id).map_err(cycle)?)?;
+            for (content, count) in day07::inside_by_color(&rules, id).map_err(cycle)? {
+                outln!("{} {}", count, name(content))?;
+            }
+        }
+        BagQuery::Path(outer, inner) => match day07::containment_path(&rules, find(outer)?, find(inner)?) {
+            Some(chain) => {
+                let steps: Vec<String> = chain.iter().skip(1).map(|&(colour, amount)| format!("{} {}", amount, name(colour))).collect();
+                outln!("{}", std::iter::once(outer.to_owned()).chain(steps).collect::<Vec<_>>().join(" -> "))?;
+            }
+            None => outln!("{} can not hold {}", outer, inner)?,
+        },
+    }
+    Ok(())
+}
+
+fn verify(options: &VerifyOptions) -> Result<(), String> {
+    let manifest = fs::read_to_string(&options.answers)
+        .map_err(|error| format!("Unable to read answers {}: {}", options.answers, error))?;
+    let answers = Answers::parse(&manifest)
+        .map_err(|error| format!("Unable to parse answers {}: {}", options.answers, error))?;
+
+    let (mut passed, mut failed) = (0, 0);
+    for entry in find_days(&options.days)? {
+        let results = match solve(entry, &InputSource::Default, runner::ALL_PARTS, &mut bench::Discard) {
+            Ok(results) => results,
+            Err(message) => {
+                outln!("Day {} FAIL: {}", entry.day, message)?;
+                failed += 1;
+                continue;
+            }
+        };
+
+        for answer in results {
+            match answers.check(entry.day, &answer) {
+                Verdict::Pass => {
+                    outln!("Day {} part {} pass: {}", entry.day, answer.part, answer.value)?;
+                    passed += 1;
+                }
+                Verdict::Mismatch { expected } => {
+                    outln!("Day {} part {} MISMATCH: expected {}, got {}", entry.day, answer.part, expected, answer.value)?;
+                    failed += 1;
+                }
+                Verdict::Missing => outln!("Day {} part {} has no expected answer, got {}", entry.day, answer.part, answer.value)?,
+            }
+        }
+    }
+
+    outln!("{} passed, {} failed", passed, failed)?;
+    if failed > 0 {
+        return Err(format!("{} checks did not pass", failed));
+    }
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(|command| command.as_str()) {
+        Some("run") => parse_run_options(&args[1..]).and_then(|options| run(&options)),
+        Some("verify") => parse_verify_options(&args[1..]).and_then(|options| verify(&options)),
+        Some("bench") => parse_bench_options(&args[1..]).and_then(|options| bench(&options)),
+        Some("compare") => parse_compare_options(&args[1..]).and_then(|options| compare(&options)),
+        Some("cubes") => parse_cubes_options(&args[1..]).and_then(|options| cubes(&options)),
+        Some("console") => parse_console_options(&args[1..]).and_then(|options| console(&options)),
+        Some("generate") => parse_generate_options(&args[1..]).and_then(|options| generate(&options)),
+        Some("bags") => parse_bags_options(&args[1..]).and_then(|options| bags(&options)),
+        _ => Err(USAGE.to_owned()),
+    };
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|&arg| arg.to_owned()).collect()
+    }
+
+    #[test]
+    fn will_parse_single_day() {
+        assert_eq!(parse_run_options(&args(&["7"])), Ok(RunOptions {
+            days: vec![7],
+            parts: vec![Part::One, Part::Two],
+            input: InputSource::Default,
+            time: false,
+            quiet: false,
+        }));
+        assert_eq!(parse_run_options(&args(&["7", "--part", "2"])), Ok(RunOptions {
+            days: vec![7],
+            parts: vec![Part::Two],
+            input: InputSource::Default,
+            time: false,
+            quiet: false,
+        }));
+
+        let options = parse_run_options(&args(&["7", "--time", "--quiet"])).unwrap();
+        assert_eq!((options.time, options.quiet), (true, true));
+    }
+
+    #[test]
+    fn will_parse_input() {
+        assert_eq!(parse_run_options(&args(&["7", "--input", "other.txt"])).unwrap().input, InputSource::Path("other.txt".to_owned()));
+        assert_eq!(parse_run_options(&args(&["7", "--input", "-"])).unwrap().input, InputSource::Stdin);
+        assert!(parse_run_options(&args(&["all", "--input", "-"])).is_err());
+        assert!(parse_run_options(&args(&["7", "--input"])).is_err());
+    }
+
+    #[test]
+    fn will_parse_all_days() {
+        let options = parse_run_options(&args(&["all"])).unwrap();
+        assert_eq!(options.days.len(), runner::DAYS.len());
+    }
+
+    #[test]
+    fn will_parse_verify_options() {
+        let options = parse_verify_options(&args(&[])).unwrap();
+        assert_eq!(options.days.len(), runner::DAYS.len());
+        assert_eq!(options.answers, Answers::default_path());
+
+        assert_eq!(parse_verify_options(&args(&["7", "--answers", "other.toml"])), Ok(VerifyOptions {
+            days: vec![7],
+            answers: "other.toml".to_owned(),
+        }));
+    }
+
+    #[test]
+    fn will_parse_bench_options() {
+        assert_eq!(parse_bench_options(&args(&["6"])), Ok(BenchOptions {
+            days: vec![6],
+            input: InputSource::Default,
+            config: bench::Config::auto(),
+            format: None,
+            output: None,
+        }));
+
+        let options = parse_bench_options(&args(&["all", "--iterations", "10", "--warmup", "0"])).unwrap();
+        assert_eq!(options.config, bench::Config { warmup: 0, iterations: Iterations::Fixed(10) });
+        assert!(parse_bench_options(&args(&["6", "--iterations", "many"])).is_err());
+        assert!(parse_bench_options(&args(&["6", "--iterations", "0"])).is_err());
+
+        let options = parse_bench_options(&args(&["6", "--format", "csv", "--output", "bench.csv"])).unwrap();
+        assert_eq!((options.format, options.output), (Some(Format::Csv), Some("bench.csv".to_owned())));
+        assert!(parse_bench_options(&args(&["6", "--format", "xml"])).is_err());
+        assert!(parse_bench_options(&args(&["6", "--output", "bench.txt"])).is_err());
+    }
+
+    #[test]
+    fn will_parse_compare_options() {
+        assert_eq!(parse_compare_options(&args(&["old.json", "new.csv", "--threshold", "5"])), Ok(CompareOptions {
+            baseline: "old.json".to_owned(),
+            current: "new.csv".to_owned(),
+            threshold: 0.05,
+        }));
+        assert_eq!(parse_compare_options(&args(&["old.json", "new.json"])).unwrap().threshold, 0.1);
+        assert!(parse_compare_options(&args(&["old.json"])).is_err());
+    }
+
+    #[test]
+    fn will_parse_cubes_options() {
+        assert_eq!(parse_cubes_options(&args(&["--dimensions", "5", "--cycles", "10", "--every-cycle", "--full"])), Ok(CubesOptions {
+            input: InputSource::Default,
+            dimensions: 5,
+            cycles: 10,
+            every_cycle: true,
+            evolution: Evolution::Full,
+            render: false,
+        }));
+        assert!(parse_cubes_options(&args(&["--render"])).unwrap().render);
+        assert_eq!(parse_cubes_options(&args(&[])).unwrap().evolution, Evolution::Mirrored);
+        assert!(parse_cubes_options(&args(&["--dimensions", "1"])).is_err());
+        assert!(parse_cubes_options(&args(&["--cycles", "1000"])).is_err());
+    }
+
+    #[test]
+    fn will_parse_console_options() {
+        assert_eq!(parse_console_options(&args(&[])), Ok(ConsoleOptions {
+            input: InputSource::Default,
+            mode: ConsoleMode::Run,
+            assembly: false,
+            values: vec![],
+            loop_detection: true,
+            max_steps: None,
+        }));
+        assert_eq!(parse_console_options(&args(&["--trace", "--assembly", "--input", "-"])), Ok(ConsoleOptions {
+            input: InputSource::Stdin,
+            mode: ConsoleMode::Trace,
+            assembly: true,
+            values: vec![],
+            loop_detection: true,
+            max_steps: None,
+        }));
+        assert_eq!(parse_console_options(&args(&["--values", "3,-1, 0", "--no-loop-detection", "--max-steps", "500"])), Ok(ConsoleOptions {
+            input: InputSource::Default,
+            mode: ConsoleMode::Run,
+            assembly: false,
+            values: vec![3, -1, 0],
+            loop_detection: false,
+            max_steps: Some(500),
+        }));
+        assert!(parse_console_options(&args(&["--values", "1,x"])).is_err());
+        assert!(parse_console_options(&args(&["--max-steps", "-1"])).is_err());
+        assert_eq!(parse_console_options(&args(&["--debug"])).unwrap().mode, ConsoleMode::Debug);
+        assert_eq!(parse_console_options(&args(&["--dot"])).unwrap().mode, ConsoleMode::Dot);
+        assert!(parse_console_options(&args(&["--debug", "--input", "-"])).is_err());
+    }
+
+    #[test]
+    fn will_parse_generate_options() {
+        assert_eq!(parse_generate_options(&args(&["--size", "50000", "--seed", "7", "--output", "big.txt"])), Ok(GenerateOptions {
+            size: 50000,
+            seed: Some(7),
+            output: Some("big.txt".to_owned()),
+        }));
+        assert_eq!(parse_generate_options(&args(&[])).unwrap().seed, None);
+        assert!(parse_generate_options(&args(&["--size", "0"])).is_err());
+        assert!(parse_generate_options(&args(&["--seed", "-1"])).is_err());
+    }
+
+    #[test]
+    fn will_parse_bags_options() {
+        assert_eq!(parse_bags_options(&args(&["--contains", "shiny gold"])), Ok(BagsOptions {
+            input: InputSource::Default,
+            query: BagQuery::Contains("shiny gold".to_owned()),
+        }));
+        assert_eq!(parse_bags_options(&args(&["--input", "-", "--path", "light red", "faded blue"])), Ok(BagsOptions {
+            input: InputSource::Stdin,
+            query: BagQuery::Path("light red".to_owned(), "faded blue".to_owned()),
+        }));
+        assert!(parse_bags_options(&args(&[])).is_err());
+        assert!(parse_bags_options(&args(&["--path", "light red"])).is_err());
+        assert!(parse_bags_options(&args(&["--inside", "light red", "--contains", "faded blue"])).is_err());
+    }
+
+    #[test]
+    fn will_reject_invalid_arguments() {
+        assert!(parse_run_options(&args(&[])).is_err());
+        assert!(parse_run_options(&args(&["seven"])).is_err());
+        assert!(parse_run_options(&args(&["7", "--part", "3"])).is_err());
+        assert_eq!(parse_run_options(&args(&["5", "7"])), Err("Unexpected second day '7'".to_owned()));
+        assert!(parse_run_options(&args(&["all", "--quiet", "7"])).is_err());
+        assert!(parse_verify_options(&args(&["5", "7"])).is_err());
+        assert!(parse_bench_options(&args(&["5", "all"])).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+use std::{collections::HashMap, fmt, time::Duration};
+use nom::{IResult, branch::alt, bytes::complete::{escaped_transform, tag}, character::complete::{char, digit1, line_ending, multispace0, none_of}, combinator::{map, map_res, opt, value}, multi::{fold_many0, many0, separated_list0}, sequence::{delimited, preceded, separated_pair, terminated, tuple}};
+use crate::runner::Part;
+use crate::util::bench::Measurement;
+use crate::util::parser::{parse_all, ParseError};
+
+/// A single benchmark measurement attributed to a day, in a form that can be stored and compared across commits.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Record {
+    pub day: u32,
+    pub part: Option<Part>,
+    pub phase: String,
+    pub iterations: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+impl Record {
+    /// Measurements named `part N` are the regular solve of that part, `part N - variant` an alternative
+    /// implementation of it. Anything else, such as input preparation, is not attributed to a part.
+    pub fn new(day: u32, measurement: &Measurement) -> Record {
+        let (part, phase) = match measurement.name.strip_prefix("part ") {
+            Some(rest) => {
+                let (part, variant) = match rest.find(" - ") {
+                    Some(index) => (&rest[..index], &rest[index + 3..]),
+                    None => (rest, "solve"),
+                };
+                match part {
+                    "1" => (Some(Part::One), variant),
+                    "2" => (Some(Part::Two), variant),
+                    _ => (None, measurement.name.as_str()),
+                }
+            }
+            None => (None, measurement.name.as_str()),
+        };
+
+        Record {
+            day,
+            part,
+            phase: phase.to_owned(),
+            iterations: measurement.iterations,
+            min: measurement.min,
+            median: measurement.median,
+            mean: measurement.mean,
+            stddev: measurement.stddev,
+        }
+    }
+
+    fn key(&self) -> (u32, Option<Part>, &str) {
+        (self.day, self.part, &self.phase)
+    }
+}
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.part {
+            Some(part) => write!(f, "Day {} part {} '{}'", self.day, part, self.phase),
+            None => write!(f, "Day {} '{}'", self.day, self.phase),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+const CSV_HEADER: &str = "day,part,phase,iterations,min_ns,median_ns,mean_ns,stddev_ns";
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+fn escape_json(string: &str) -> String {
+    string.chars().fold(String::new(), |mut escaped, c| {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+        escaped
+    })
+}
+
+impl Format {
+    pub fn from_arg(arg: &str) -> Option<Format> {
+        match arg {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+
+    pub fn write(&self, records: &[Record]) -> String {
+        match self {
+            Format::Json => {
+                let objects: Vec<String> = records.iter().map(|record| format!(
+                    "  {{\"day\": {}, \"part\": {}, \"phase\": \"{}\", \"iterations\": {}, \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"stddev_ns\": {}}}",
+                    record.day,
+                    record.part.map_or("null".to_owned(), |part| part.to_string()),
+                    escape_json(&record.phase),
+                    record.iterations,
+                    nanos(record.min), nanos(record.median), nanos(record.mean), nanos(record.stddev)
+                )).collect();
+                format!("[\n{}\n]\n", objects.join(",\n"))
+            }
+            Format::Csv => records.iter().fold(format!("{}\n", CSV_HEADER), |mut csv, record| {
+                csv.push_str(&format!(
+                    "{},{},\"{}\",{},{},{},{},{}\n",
+                    record.day,
+                    record.part.map_or(String::new(), |part| part.to_string()),
+                    record.phase.replace('"', "\"\""),
+                    record.iterations,
+                    nanos(record.min), nanos(record.median), nanos(record.mean), nanos(record.stddev)
+                ));
+                csv
+            }),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum JsonValue {
+    Null,
+    Number(u64),
+    String(String),
+}
+
+fn parse_number(i: &str) -> IResult<&str, u64> {
+    map_res(digit1, |number: &str| number.parse())(i)
+}
+
+fn parse_json_string(i: &str) -> IResult<&str, String> {
+    delimited(
+        char('"'),
+        map(opt(escaped_transform(none_of("\\\""), '\\', alt((
+            value("\\", char('\\')),
+            value("\"", char('"')),
+            value("\n", char('n')),
+        )))), Option::unwrap_or_default),
+        char('"')
+    )(i)
+}
+
+fn parse_json_value(i: &str) -> IResult<&str, JsonValue> {
+    alt((
+        value(JsonValue::Null, tag("null")),
+        map(parse_number, JsonValue::Number),
+        map(parse_json_string, JsonValue::String),
+    ))(i)
+}
+
+fn parse_part(part: u64) -> Result<Part, String> {
+    match part {
+        1 => Ok(Part::One),
+        2 => Ok(Part::Two),
+        part => Err(format!("Invalid part {}", part)),
+    }
+}
+
+fn record_from_fields(fields: Vec<(String, JsonValue)>) -> Result<Record, String> {
+    let fields: HashMap<String, JsonValue> = fields.into_iter().collect();
+    let number = |name: &str| match fields.get(name) {
+        Some(JsonValue::Number(number)) => Ok(*number),
+        _ => Err(format!("Expected a number for '{}'", name)),
+    };
+    let duration = |name: &str| number(name).map(Duration::from_nanos);
+
+    Ok(Record {
+        day: number("day")? as u32,
+        part: match fields.get("part") {
+            Some(JsonValue::Null) | None => None,
+            Some(JsonValue::Number(part)) => Some(parse_part(*part)?),
+            Some(_) => return Err("Expected a number or null for 'part'".to_owned()),
+        },
+        phase: match fields.get("phase") {
+            Some(JsonValue::String(phase)) => phase.to_owned(),
+            _ => return Err("Expected a string for 'phase'".to_owned()),
+        },
+        iterations: number("iterations")? as u32,
+        min: duration("min_ns")?,
+        median: duration("median_ns")?,
+        mean: duration("mean_ns")?,
+        stddev: duration("stddev_ns")?,
+    })
+}
+
+fn parse_json_record(i: &str) -> IResult<&str, Record> {
+    map_res(
+        delimited(
+            terminated(char('{'), multispace0),
+            separated_list0(
+                tuple((multispace0, char(','), multispace0)),
+                separated_pair(parse_json_string, tuple((multispace0, char(':'), multispace0)), parse_json_value)
+            ),
+            preceded(multispace0, char('}'))
+        ),
+        record_from_fields
+    )(i)
+}
+
+fn parse_json(i: &str) -> IResult<&str, Vec<Record>> {
+    delimited(
+        terminated(char('['), multispace0),
+        separated_list0(tuple((multispace0, char(','), multispace0)), parse_json_record),
+        tuple((multispace0, char(']'), multispace0))
+    )(i)
+}
+
+fn parse_csv_phase(i: &str) -> IResult<&str, String> {
+    delimited(
+        char('"'),
+        fold_many0(alt((none_of("\""), value('"', tag("\"\"")))), String::new(), |mut phase, c| {
+            phase.push(c);
+            phase
+        }),
+        char('"')
+    )(i)
+}
+
+fn parse_csv_record(i: &str) -> IResult<&str, Record> {
+    map_res(
+        tuple((
+            terminated(parse_number, char(',')),
+            terminated(opt(parse_number), char(',')),
+            terminated(parse_csv_phase, char(',')),
+            terminated(parse_number, char(',')),
+            terminated(parse_number, char(',')),
+            terminated(parse_number, char(',')),
+            terminated(parse_number, char(',')),
+            parse_number,
+        )),
+        |(day, part, phase, iterations, min, median, mean, stddev)| -> Result<Record, String> {
+            Ok(Record {
+                day: day as u32,
+                part: part.map(parse_part).transpose()?,
+                phase,
+                iterations: iterations as u32,
+                min: Duration::from_nanos(min),
+                median: Duration::from_nanos(median),
+                mean: Duration::from_nanos(mean),
+                stddev: Duration::from_nanos(stddev),
+            })
+        }
+    )(i)
+}
+
+fn parse_csv(i: &str) -> IResult<&str, Vec<Record>> {
+    preceded(
+        terminated(tag(CSV_HEADER), line_ending),
+        many0(terminated(parse_csv_record, opt(line_ending)))
+    )(i)
+}
+
+/// Reads records written by `Format::write`, detecting whether they are JSON or CSV.
+pub fn parse(input: &str) -> Result<Vec<Record>, ParseError> {
+    if input.trim_start().starts_with('[') {
+        parse_all(input, preceded(multispace0, parse_json))
+    } else {
+        parse_all(input, parse_csv)
+    }
+}
+
+/// The change in median time of a measurement present in both a baseline and a current run.
+#[derive(Debug, PartialEq)]
+pub struct Comparison<'a> {
+    pub baseline: &'a Record,
+    pub current: &'a Record,
+}
+impl Comparison<'_> {
+    /// The relative change of the median, where `0.1` means 10% slower.
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.median.as_secs_f64().max(1e-9);
+        self.current.median.as_secs_f64() / baseline - 1.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+/// Pairs every current record with the baseline record of the same day, part and phase. Records only present
+/// in one of both are left out.
+pub fn compare<'a>(baseline: &'a [Record], current: &'a [Record]) -> Vec<Comparison<'a>> {
+    let baseline: HashMap<_, _> = baseline.iter().map(|record| (record.key(), record)).collect();
+    current.iter().filter_map(|current| {
+        baseline.get(&current.key()).map(|&baseline| Comparison {
+            baseline,
+            current,
+        })
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(name: &str, median_us: u64) -> Measurement {
+        let median = Duration::from_micros(median_us);
+        Measurement {
+            name: name.to_owned(),
+            iterations: 10,
+            warmup: 3,
+            min: median / 2,
+            median,
+            mean: median,
+            stddev: Duration::from_nanos(1234),
+        }
+    }
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::new(6, &measurement("input preparation", 100)),
+            Record::new(6, &measurement("part 1", 30)),
+            Record::new(6, &measurement("part 2 - prepared \"counts\"", 2)),
+        ]
+    }
+
+    #[test]
+    fn will_attribute_measurements_to_parts() {
+        let records = records();
+        assert_eq!((records[0].part, records[0].phase.as_str()), (None, "input preparation"));
+        assert_eq!((records[1].part, records[1].phase.as_str()), (Some(Part::One), "solve"));
+        assert_eq!((records[2].part, records[2].phase.as_str()), (Some(Part::Two), "prepared \"counts\""));
+    }
+
+    #[test]
+    fn will_read_written_records() {
+        for format in &[Format::Json, Format::Csv] {
+            assert_eq!(parse(&format.write(&records())), Ok(records()));
+            assert_eq!(parse(&format.write(&[])), Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn will_locate_invalid_records() {
+        let error = parse("[\n  {\"day\": 6, \"part\": 3}\n]").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = parse(&format!("{}\n6,,\"parse\",1,2,3,4\n", CSV_HEADER)).unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn will_flag_regressions() {
+        let baseline = records();
+        let current = vec![
+            Record::new(6, &measurement("part 1", 36)),
+            Record::new(6, &measurement("part 2 - prepared \"counts\"", 2)),
+            Record::new(7, &measurement("part 1", 1)),
+        ];
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 2);
+        assert!((comparisons[0].change() - 0.2).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(0.1));
+        assert!(!comparisons[0].is_regression(0.25));
+        assert!(!comparisons[1].is_regression(0.0));
+    }
+}
@@ -0,0 +1,169 @@
+use std::{fmt, fs, io::{self, Read}};
+use crate::util::bench::{Bencher, Config, Sink};
+use crate::util::parser::ParseError;
+use crate::solution::Solution;
+use crate::{day03, day05, day06, day07, day08, day09, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19};
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+pub const ALL_PARTS: &[Part] = &[Part::One, Part::Two];
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+}
+
+/// Where a day reads its puzzle input from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InputSource {
+    /// The `input.txt` committed next to the day's solution.
+    Default,
+    Path(String),
+    Stdin,
+}
+impl InputSource {
+    /// Interprets a command line argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> InputSource {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(path.to_owned()),
+        }
+    }
+
+    pub fn describe(&self, day: &Day) -> String {
+        match self {
+            InputSource::Default => day.input_path(),
+            InputSource::Path(path) => path.to_owned(),
+            InputSource::Stdin => "<stdin>".to_owned(),
+        }
+    }
+
+    pub fn read(&self, day: &Day) -> io::Result<String> {
+        match self {
+            InputSource::Default => fs::read_to_string(day.input_path()),
+            InputSource::Path(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+type SolveFn = fn(&str, &[Part], &mut dyn Sink) -> Result<Vec<Answer>, ParseError>;
+
+pub struct Day {
+    pub day: u32,
+    pub solve: SolveFn,
+    pub bench: fn(&str, &mut Bencher) -> Result<(), ParseError>,
+}
+impl Day {
+    pub const fn of<S: Solution>(day: u32) -> Day {
+        Day {
+            day,
+            solve: solve::<S>,
+            bench: bench::<S>,
+        }
+    }
+
+    /// The committed input, resolved against the crate root so it can be found from any working directory.
+    pub fn input_path(&self) -> String {
+        format!("{}/src/day{:02}/input.txt", env!("CARGO_MANIFEST_DIR"), self.day)
+    }
+}
+
+/// Solves the requested parts once, reporting how long each step took to `timings` rather than printing it,
+/// so callers decide whether timings are shown at all.
+pub fn solve<S: Solution>(input: &str, parts: &[Part], timings: &mut dyn Sink) -> Result<Vec<Answer>, ParseError> {
+    let mut bencher = Bencher::new(Config::once(), timings);
+    let prepared_input = bencher.run("input preparation", || S::parse(input))?;
+    Ok(parts.iter().map(|&part| {
+        let value = match part {
+            Part::One => bencher.run("part 1", || S::part1(&prepared_input)).to_string(),
+            Part::Two => bencher.run("part 2", || S::part2(&prepared_input)).to_string(),
+        };
+        Answer { part, value }
+    }).collect())
+}
+
+pub fn bench<S: Solution>(input: &str, bencher: &mut Bencher) -> Result<(), ParseError> {
+    let prepared_input = bencher.run("input preparation", || S::parse(input))?;
+    bencher.run("part 1", || S::part1(&prepared_input));
+    bencher.run("part 2", || S::part2(&prepared_input));
+    S::bench_variants(input, bencher);
+    Ok(())
+}
+
+/// Every registered solution, ordered by day. New days only need to be added here to be picked up by the runner.
+pub const DAYS: &[Day] = &[
+    Day::of::<day03::Day03>(3),
+    Day::of::<day05::Day05>(5),
+    Day::of::<day06::Day06>(6),
+    Day::of::<day07::Day07>(7),
+    Day::of::<day08::Day08>(8),
+    Day::of::<day09::Day09>(9),
+    Day::of::<day10::Day10>(10),
+    Day::of::<day11::Day11>(11),
+    Day::of::<day12::Day12>(12),
+    Day::of::<day13::Day13>(13),
+    Day::of::<day14::Day14>(14),
+    Day::of::<day15::Day15>(15),
+    Day::of::<day16::Day16>(16),
+    Day::of::<day17::Day17>(17),
+    Day::of::<day18::Day18>(18),
+    Day::of::<day19::Day19>(19),
+];
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|entry| entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::bench::Measurement;
+
+    #[test]
+    fn days_are_ordered_and_unique() {
+        assert!(DAYS.windows(2).all(|pair| pair[0].day < pair[1].day));
+    }
+
+    #[test]
+    fn will_read_input_from_path() {
+        let entry = find(15).unwrap();
+        let input = InputSource::from_arg(&entry.input_path()).read(entry).unwrap();
+        assert_eq!(input, InputSource::Default.read(entry).unwrap());
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+    }
+
+    #[test]
+    fn will_report_timings_separately_from_answers() {
+        let mut timings: Vec<Measurement> = vec![];
+        let answers = (find(15).unwrap().solve)("0,3,6", &[Part::One], &mut timings).unwrap();
+
+        assert_eq!(answers, vec![Answer { part: Part::One, value: "436".to_owned() }]);
+        let names: Vec<&str> = timings.iter().map(|measurement| measurement.name.as_str()).collect();
+        assert_eq!(names, vec!["input preparation", "part 1"]);
+    }
+
+    #[test]
+    fn will_find_day() {
+        assert_eq!(find(7).map(|entry| entry.day), Some(7));
+        assert!(find(4).is_none());
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt::Display;
+use crate::util::{bench::Bencher, parser::ParseError};
+
+/// A single day's puzzle. The input is parsed once and both parts are solved from the parsed representation,
+/// so generic tooling (the runner, benchmarks, verification) can drive every day the same way.
+pub trait Solution {
+    type Input<'a>;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part1(input: &Self::Input<'_>) -> Self::Output1;
+    fn part2(input: &Self::Input<'_>) -> Self::Output2;
+
+    /// Benchmarks alternative implementations alongside the regular parts. Most days have none.
+    fn bench_variants(_input: &str, _bencher: &mut Bencher) {}
+}
@@ -1,3 +1,8 @@
 pub mod bench;
 pub mod parser;
-pub mod bitset;
\ No newline at end of file
+pub mod bitset;
+pub mod grid;
+pub mod automaton;
+pub mod cycle;
+pub mod graph;
+pub mod random;
\ No newline at end of file
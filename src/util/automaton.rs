@@ -0,0 +1,431 @@
+use std::{collections::{HashMap, HashSet}, hash::Hash, marker::PhantomData};
+use crate::util::cycle::{self, Repetition};
+use crate::util::grid::{Grid, Neighbourhood};
+
+/// Decides whether a cell is alive in the next generation from whether it is alive now and how many of its
+/// neighbours are.
+pub trait Rule {
+    fn next(&self, alive: bool, live_neighbours: usize) -> bool;
+}
+
+impl<F> Rule for F
+where F: Fn(bool, usize) -> bool
+{
+    fn next(&self, alive: bool, live_neighbours: usize) -> bool {
+        self(alive, live_neighbours)
+    }
+}
+
+/// A rule in the style of Conway's game of life: dead cells are born with one of the `birth` counts of live
+/// neighbours, and live cells survive with one of the `survival` counts.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LifeLike {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+impl LifeLike {
+    pub fn new(birth: &[usize], survival: &[usize]) -> LifeLike {
+        LifeLike {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+
+    /// Conway's rule, B3/S23.
+    pub fn conway() -> LifeLike {
+        LifeLike::new(&[3], &[2, 3])
+    }
+}
+impl Rule for LifeLike {
+    fn next(&self, alive: bool, live_neighbours: usize) -> bool {
+        match alive {
+            false => self.birth.contains(&live_neighbours),
+            true => self.survival.contains(&live_neighbours),
+        }
+    }
+}
+
+/// The space cells live in: how a generation is stored and which cells neighbour each other.
+pub trait Topology {
+    type State;
+    /// Identifies a generation, so repeating generations can be recognised.
+    type Key: Hash + Eq;
+
+    /// Computes the next generation, and whether it differs from the current one.
+    fn step<R: Rule>(&self, state: &Self::State, rule: &R) -> (Self::State, bool);
+
+    fn population(&self, state: &Self::State) -> usize;
+
+    fn key(&self, state: &Self::State) -> Self::Key;
+}
+
+type Position = (usize, usize);
+
+/// A bounded grid where only some cells take part, each with a fixed list of neighbours. Cells that do not take
+/// part keep their state forever.
+#[derive(Debug, Clone)]
+pub struct GridTopology {
+    width: usize,
+    height: usize,
+    cells: Vec<(Position, Vec<Position>)>,
+}
+impl GridTopology {
+    /// Every cell takes part, neighbouring the cells of `neighbourhood` that lie inside the grid.
+    pub fn new(width: usize, height: usize, neighbourhood: Neighbourhood) -> GridTopology {
+        let grid = Grid::filled(width, height, ());
+        GridTopology::with_neighbours(width, height, |x, y| Some(grid.neighbour_positions(x, y, neighbourhood).collect()))
+    }
+
+    /// Asks `neighbours` for the neighbours of every cell up front, where `None` means the cell does not take part.
+    pub fn with_neighbours<F>(width: usize, height: usize, neighbours: F) -> GridTopology
+    where F: Fn(usize, usize) -> Option<Vec<Position>>
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| neighbours(x, y).map(|neighbours| ((x, y), neighbours)))
+            .collect();
+
+        GridTopology {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+impl Topology for GridTopology {
+    type State = Grid<bool>;
+    type Key = Grid<bool>;
+
+    fn step<R: Rule>(&self, state: &Grid<bool>, rule: &R) -> (Grid<bool>, bool) {
+        assert_eq!((state.width(), state.height()), (self.width, self.height), "State does not match the topology");
+
+        let mut next = state.clone();
+        let mut changed = false;
+        for (position, neighbours) in &self.cells {
+            let alive = state[*position];
+            let live_neighbours = neighbours.iter().filter(|&&neighbour| state[neighbour]).count();
+            let next_alive = rule.next(alive, live_neighbours);
+            if next_alive != alive {
+                next[*position] = next_alive;
+                changed = true;
+            }
+        }
+        (next, changed)
+    }
+
+    fn population(&self, state: &Grid<bool>) -> usize {
+        state.cells().filter(|&&alive| alive).count()
+    }
+
+    fn key(&self, state: &Grid<bool>) -> Grid<bool> {
+        state.clone()
+    }
+}
+
+/// A position in an unbounded lattice.
+pub trait Point: Hash + Ord + Clone {
+    /// Calls `f` with every neighbouring position, excluding the position itself.
+    fn for_each_neighbour<F: FnMut(Self)>(&self, f: F);
+}
+
+/// A point in a lattice with a dimension fixed at compile time, neighbouring every point that differs by at most
+/// one in every coordinate. Visiting neighbours does not allocate.
+impl<const N: usize> Point for [i8; N] {
+    fn for_each_neighbour<F: FnMut([i8; N])>(&self, mut f: F) {
+        let mut offsets = [-1; N];
+        loop {
+            if offsets.iter().any(|&offset| offset != 0) {
+                let mut neighbour = *self;
+                for (coordinate, offset) in neighbour.iter_mut().zip(offsets.iter()) {
+                    *coordinate += offset;
+                }
+                f(neighbour);
+            }
+
+            // Counts through every combination of offsets as a base 3 number.
+            let mut dimension = 0;
+            loop {
+                if dimension == N {
+                    return;
+                }
+                if offsets[dimension] < 1 {
+                    offsets[dimension] += 1;
+                    break;
+                }
+                offsets[dimension] = -1;
+                dimension += 1;
+            }
+        }
+    }
+}
+
+/// An unbounded space storing only the live cells. Dead cells without live neighbours stay dead, so rules
+/// that give birth at zero neighbours are not supported.
+#[derive(Debug, Default, Clone)]
+pub struct SparseTopology<P> {
+    point: PhantomData<P>,
+}
+impl<P: Point> SparseTopology<P> {
+    pub fn new() -> SparseTopology<P> {
+        SparseTopology {
+            point: PhantomData,
+        }
+    }
+}
+impl<P: Point> Topology for SparseTopology<P> {
+    type State = HashSet<P>;
+    type Key = Vec<P>;
+
+    fn step<R: Rule>(&self, state: &HashSet<P>, rule: &R) -> (HashSet<P>, bool) {
+        let mut live_neighbours: HashMap<P, usize> = state.iter().map(|position| (position.clone(), 0)).collect();
+        for position in state {
+            position.for_each_neighbour(|neighbour| *live_neighbours.entry(neighbour).or_insert(0) += 1);
+        }
+
+        let next: HashSet<P> = live_neighbours.into_iter()
+            .filter(|(position, count)| rule.next(state.contains(position), *count))
+            .map(|(position, _)| position)
+            .collect();
+        let changed = next != *state;
+        (next, changed)
+    }
+
+    fn population(&self, state: &HashSet<P>) -> usize {
+        state.len()
+    }
+
+    fn key(&self, state: &HashSet<P>) -> Vec<P> {
+        let mut points: Vec<P> = state.iter().cloned().collect();
+        points.sort();
+        points
+    }
+}
+
+/// A sparse lattice whose state is symmetric under flipping the sign of any coordinate from `mirrored_from` on,
+/// such as a planar seed extended into extra dimensions. Only the canonical positions, where all of those
+/// coordinates are non-negative, are stored; each stands for every reflection of itself.
+#[derive(Debug, Clone)]
+pub struct MirroredTopology<const N: usize> {
+    mirrored_from: usize,
+}
+impl<const N: usize> MirroredTopology<N> {
+    pub fn new(mirrored_from: usize) -> MirroredTopology<N> {
+        assert!(mirrored_from <= N, "Cannot mirror from dimension {} of {}", mirrored_from, N);
+        MirroredTopology {
+            mirrored_from,
+        }
+    }
+
+    pub fn is_canonical(&self, position: &[i8; N]) -> bool {
+        position[self.mirrored_from..].iter().all(|&coordinate| coordinate >= 0)
+    }
+
+    pub fn canonical(&self, position: &[i8; N]) -> [i8; N] {
+        let mut canonical = *position;
+        for coordinate in &mut canonical[self.mirrored_from..] {
+            *coordinate = coordinate.abs();
+        }
+        canonical
+    }
+
+    /// How many positions of the full lattice a canonical position stands for.
+    pub fn weight(&self, position: &[i8; N]) -> usize {
+        1 << position[self.mirrored_from..].iter().filter(|&&coordinate| coordinate != 0).count()
+    }
+}
+impl<const N: usize> Topology for MirroredTopology<N> {
+    type State = HashSet<[i8; N]>;
+    type Key = Vec<[i8; N]>;
+
+    fn step<R: Rule>(&self, state: &HashSet<[i8; N]>, rule: &R) -> (HashSet<[i8; N]>, bool) {
+        let mut live_neighbours: HashMap<[i8; N], usize> = state.iter().map(|&position| (position, 0)).collect();
+        for position in state {
+            position.for_each_neighbour(|neighbour| {
+                if !self.is_canonical(&neighbour) {
+                    return;
+                }
+                // A coordinate of one borders zero from both sides, so both reflections of the position are
+                // neighbours of the canonical one.
+                let reflections = (self.mirrored_from..N)
+                    .filter(|&dimension| position[dimension] == 1 && neighbour[dimension] == 0)
+                    .count();
+                *live_neighbours.entry(neighbour).or_insert(0) += 1 << reflections;
+            });
+        }
+
+        let next: HashSet<[i8; N]> = live_neighbours.into_iter()
+            .filter(|(position, count)| rule.next(state.contains(position), *count))
+            .map(|(position, _)| position)
+            .collect();
+        let changed = next != *state;
+        (next, changed)
+    }
+
+    fn population(&self, state: &HashSet<[i8; N]>) -> usize {
+        state.iter().map(|position| self.weight(position)).sum()
+    }
+
+    fn key(&self, state: &HashSet<[i8; N]>) -> Vec<[i8; N]> {
+        let mut points: Vec<[i8; N]> = state.iter().cloned().collect();
+        points.sort();
+        points
+    }
+}
+
+/// Evolves a state generation by generation according to a rule over a topology.
+pub struct Automaton<T: Topology, R: Rule> {
+    topology: T,
+    rule: R,
+    state: T::State,
+    generation: usize,
+}
+impl<T: Topology, R: Rule> Automaton<T, R> {
+    pub fn new(topology: T, rule: R, initial: T::State) -> Automaton<T, R> {
+        Automaton {
+            topology,
+            rule,
+            state: initial,
+            generation: 0,
+        }
+    }
+
+    /// Advances a single generation, returning whether anything changed.
+    pub fn step(&mut self) -> bool {
+        let (next, changed) = self.topology.step(&self.state, &self.rule);
+        self.state = next;
+        self.generation += 1;
+        changed
+    }
+
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Steps until a generation no longer changes, returning the generation at which the state became stable.
+    pub fn run_to_fixpoint(&mut self) -> usize {
+        while self.step() {}
+        self.generation - 1
+    }
+
+    /// Simulates from the current generation until a generation repeats, without advancing the automaton itself.
+    /// Unlike `run_to_fixpoint` this also terminates on oscillating patterns. Step numbers in the result are
+    /// relative to the current generation.
+    pub fn detect_repetition(&self, max_steps: usize) -> Option<Repetition<T::State>>
+    where T::State: Clone
+    {
+        cycle::detect_by_key(
+            self.state.clone(),
+            max_steps,
+            |state| self.topology.step(state, &self.rule).0,
+            |state| self.topology.key(state)
+        )
+    }
+
+    pub fn state(&self) -> &T::State {
+        &self.state
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn population(&self) -> usize {
+        self.topology.population(&self.state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::cycle::Outcome;
+
+    fn blinker() -> Grid<bool> {
+        Grid::parse(".....\n..#..\n..#..\n..#..\n.....", |c| c == b'.' || c == b'#').unwrap().map(|&c| c == b'#')
+    }
+
+    #[test]
+    fn will_apply_life_like_rules() {
+        let rule = LifeLike::conway();
+        assert!(rule.next(false, 3));
+        assert!(!rule.next(false, 2));
+        assert!(rule.next(true, 2));
+        assert!(!rule.next(true, 4));
+    }
+
+    #[test]
+    fn will_oscillate_on_grid() {
+        let mut automaton = Automaton::new(GridTopology::new(5, 5, Neighbourhood::Surrounding), LifeLike::conway(), blinker());
+        assert!(automaton.step());
+        assert_eq!(automaton.state().row(2), &[false, true, true, true, false]);
+        automaton.step();
+        assert_eq!(automaton.state(), &blinker());
+        assert_eq!((automaton.generation(), automaton.population()), (2, 3));
+    }
+
+    #[test]
+    fn mirrored_evolution_matches_full_evolution() {
+        let seed: HashSet<[i8; 4]> = vec![[0, -1, 0, 0], [1, 0, 0, 0], [-1, 1, 0, 0], [0, 1, 0, 0], [1, 1, 0, 0]].into_iter().collect();
+        let mut full = Automaton::new(SparseTopology::new(), LifeLike::conway(), seed.clone());
+        let mut mirrored = Automaton::new(MirroredTopology::new(2), LifeLike::conway(), seed);
+
+        for _ in 0..4 {
+            full.step();
+            mirrored.step();
+            assert_eq!(mirrored.population(), full.population());
+
+            let topology = MirroredTopology::<4>::new(2);
+            let canonical: HashSet<[i8; 4]> = full.state().iter().map(|position| topology.canonical(position)).collect();
+            assert_eq!(mirrored.state(), &canonical);
+        }
+    }
+
+    #[test]
+    fn will_detect_oscillation() {
+        let automaton = Automaton::new(GridTopology::new(5, 5, Neighbourhood::Surrounding), LifeLike::conway(), blinker());
+        let repetition = automaton.detect_repetition(10).unwrap();
+        assert_eq!(repetition.outcome(), Outcome::Cycle { start: 0, length: 2 });
+        assert_eq!(repetition.state_at(1001).row(2), &[false, true, true, true, false]);
+
+        let blinker: HashSet<[i8; 2]> = vec![[0, -1], [0, 0], [0, 1]].into_iter().collect();
+        let automaton = Automaton::new(SparseTopology::new(), LifeLike::conway(), blinker);
+        assert_eq!(automaton.detect_repetition(10).unwrap().outcome(), Outcome::Cycle { start: 0, length: 2 });
+    }
+
+    #[test]
+    fn will_run_to_fixpoint() {
+        let block = Grid::parse("....\n.##.\n.#..\n....", |c| c == b'.' || c == b'#').unwrap().map(|&c| c == b'#');
+        let mut automaton = Automaton::new(GridTopology::new(4, 4, Neighbourhood::Surrounding), LifeLike::conway(), block);
+        assert_eq!(automaton.run_to_fixpoint(), 1);
+        assert_eq!(automaton.population(), 4);
+    }
+
+    #[test]
+    fn will_leave_cells_outside_topology_alone() {
+        let topology = GridTopology::with_neighbours(5, 5, |x, _| if x == 2 { None } else { Some(vec![]) });
+        let mut automaton = Automaton::new(topology, |_, _| false, blinker());
+        automaton.step();
+        assert_eq!(automaton.state(), &blinker());
+    }
+
+    #[test]
+    fn will_evolve_sparse_points() {
+        let mut neighbours = 0;
+        [0, 0, 0].for_each_neighbour(|_| neighbours += 1);
+        assert_eq!(neighbours, 26);
+
+        let mut neighbours = HashSet::new();
+        [5, 5, 5, 5].for_each_neighbour(|neighbour| { neighbours.insert(neighbour); });
+        assert_eq!(neighbours.len(), 80);
+        assert!(!neighbours.contains(&[5, 5, 5, 5]));
+        assert!(neighbours.contains(&[4, 6, 5, 6]));
+
+        let glider: HashSet<[i8; 2]> = vec![[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]].into_iter().collect();
+        let mut automaton = Automaton::new(SparseTopology::new(), LifeLike::conway(), glider.clone());
+        automaton.run(4);
+
+        let moved: HashSet<[i8; 2]> = glider.iter().map(|position| [position[0] + 1, position[1] + 1]).collect();
+        assert_eq!(automaton.state(), &moved);
+    }
+}
@@ -1,30 +1,219 @@
-use std::time;
+use std::{fmt, hint::black_box, time::{Duration, Instant}};
 
-pub fn time<T, F>(name: &str, work: F) -> T
-where F: FnOnce() -> T
-{
-    let start = time::Instant::now();
+/// How many measured runs to perform.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Iterations {
+    /// Exactly this many runs, which must be at least one.
+    Fixed(u32),
+    /// Calibrates the count from a single timed run so that measuring takes roughly `target`, capped at `max` runs.
+    Auto { target: Duration, max: u32 },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Config {
+    /// Unmeasured runs before measuring starts.
+    pub warmup: u32,
+    pub iterations: Iterations,
+}
+impl Config {
+    pub fn once() -> Config {
+        Config {
+            warmup: 0,
+            iterations: Iterations::Fixed(1),
+        }
+    }
+
+    pub fn auto() -> Config {
+        Config {
+            warmup: 3,
+            iterations: Iterations::Auto {
+                target: Duration::from_millis(500),
+                max: 100_000,
+            },
+        }
+    }
+}
+impl Default for Config {
+    fn default() -> Config {
+        Config::auto()
+    }
+}
+
+/// Timing statistics over every measured run of a piece of work.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Measurement {
+    pub name: String,
+    pub iterations: u32,
+    pub warmup: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+impl Measurement {
+    pub fn from_samples(name: &str, warmup: u32, samples: &mut [Duration]) -> Measurement {
+        assert!(!samples.is_empty(), "Expected atleast one sample");
+        samples.sort();
 
-    let result = work();
-    
-    let end = time::Instant::now();
-    println!("Time '{}': {:?}", name, end - start);
+        let n = samples.len();
+        let total: Duration = samples.iter().sum();
+        let mean = total / n as u32;
+        let variance = samples.iter().map(|sample| {
+            let difference = sample.as_secs_f64() - mean.as_secs_f64();
+            difference * difference
+        }).sum::<f64>() / n as f64;
+
+        Measurement {
+            name: name.to_owned(),
+            iterations: n as u32,
+            warmup,
+            min: samples[0],
+            median: if n % 2 == 1 { samples[n / 2] } else { (samples[n / 2 - 1] + samples[n / 2]) / 2 },
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.iterations == 1 {
+            return write!(f, "Time '{}': {:?}", self.name, self.mean);
+        }
+        write!(
+            f, "Time '{}': {:?} ± {:?} (median {:?}, min {:?}, {} iterations)",
+            self.name, self.mean, self.stddev, self.median, self.min, self.iterations
+        )
+    }
+}
+
+/// Receives every measurement taken by a `Bencher`.
+pub trait Sink {
+    fn record(&mut self, measurement: &Measurement);
+}
+
+pub struct Stderr;
+impl Sink for Stderr {
+    fn record(&mut self, measurement: &Measurement) {
+        eprintln!("{}", measurement);
+    }
+}
+
+/// Drops every measurement, for when only the results of the work matter.
+pub struct Discard;
+impl Sink for Discard {
+    fn record(&mut self, _measurement: &Measurement) {}
+}
 
-    result
+impl Sink for Vec<Measurement> {
+    fn record(&mut self, measurement: &Measurement) {
+        self.push(measurement.to_owned());
+    }
 }
 
-pub fn time_repeat<T, F>(name: &str, work: F, n: u32) -> T
-where F: Fn() -> T
+/// Runs `work` according to `config`, returning the result of the last run and the timing statistics.
+pub fn measure<T, F>(name: &str, config: &Config, mut work: F) -> (T, Measurement)
+where F: FnMut() -> T
 {
-    let start = time::Instant::now();
+    assert!(config.iterations != Iterations::Fixed(0), "Cannot measure zero iterations");
+    for _ in 0..config.warmup {
+        black_box(work());
+    }
+
+    let mut timed = || {
+        let start = Instant::now();
+        let result = black_box(work());
+        (result, start.elapsed())
+    };
+
+    let (mut result, first) = timed();
+    let iterations = match config.iterations {
+        Iterations::Fixed(n) => n,
+        Iterations::Auto { target, max } => {
+            let estimate = target.as_nanos() / first.as_nanos().max(1);
+            estimate.max(1).min(max as u128) as u32
+        }
+    };
+
+    let mut samples = Vec::with_capacity(iterations as usize);
+    samples.push(first);
+    for _ in 1..iterations {
+        let (next_result, elapsed) = timed();
+        result = next_result;
+        samples.push(elapsed);
+    }
+
+    (result, Measurement::from_samples(name, config.warmup, &mut samples))
+}
+
+/// Measures several pieces of work with the same configuration, reporting each to the sink.
+pub struct Bencher<'a> {
+    config: Config,
+    sink: &'a mut dyn Sink,
+}
+impl<'a> Bencher<'a> {
+    pub fn new(config: Config, sink: &'a mut dyn Sink) -> Bencher<'a> {
+        Bencher {
+            config,
+            sink,
+        }
+    }
+
+    pub fn run<T, F>(&mut self, name: &str, work: F) -> T
+    where F: FnMut() -> T
+    {
+        let (result, measurement) = measure(name, &self.config, work);
+        self.sink.record(&measurement);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn will_compute_statistics() {
+        let mut samples: Vec<Duration> = [4, 1, 3, 2].iter().map(|&ms| Duration::from_millis(ms)).collect();
+        let measurement = Measurement::from_samples("work", 2, &mut samples);
+
+        assert_eq!(measurement.iterations, 4);
+        assert_eq!(measurement.warmup, 2);
+        assert_eq!(measurement.min, Duration::from_millis(1));
+        assert_eq!(measurement.median, Duration::from_micros(2500));
+        assert_eq!(measurement.mean, Duration::from_micros(2500));
+        assert_eq!(measurement.stddev.as_micros(), 1118);
+    }
 
-    let mut result: Option<T> = None;
-    for _ in 0..n {
-        result = Some(work());
+    #[test]
+    fn will_run_fixed_iterations_and_record() {
+        let mut runs = 0;
+        let mut measurements: Vec<Measurement> = vec![];
+
+        let result = Bencher::new(Config { warmup: 2, iterations: Iterations::Fixed(5) }, &mut measurements).run("count", || {
+            runs += 1;
+            runs
+        });
+
+        assert_eq!(runs, 7);
+        assert_eq!(result, 7);
+        assert_eq!(measurements.len(), 1);
+        assert_eq!(measurements[0].name, "count");
+        assert_eq!(measurements[0].iterations, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot measure zero iterations")]
+    fn will_reject_zero_iterations() {
+        measure("noop", &Config { warmup: 0, iterations: Iterations::Fixed(0) }, || ());
     }
-    
-    let end = time::Instant::now();
-    println!("Time '{}': {:?}", name, (end - start) / n);
 
-    result.expect("Expected work function to be executed atleast once")
-}
\ No newline at end of file
+    #[test]
+    fn auto_iterations_are_bounded() {
+        let config = Config {
+            warmup: 0,
+            iterations: Iterations::Auto { target: Duration::from_secs(10), max: 20 },
+        };
+        let (_, measurement) = measure("noop", &config, || ());
+        assert_eq!(measurement.iterations, 20);
+    }
+}
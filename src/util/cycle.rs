@@ -0,0 +1,124 @@
+use std::{collections::{HashMap, hash_map::Entry}, hash::Hash};
+
+/// How an iterated system started repeating itself.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    /// The state at `step` maps onto itself.
+    Fixpoint { step: usize },
+    /// The state at `start` comes back every `length` steps.
+    Cycle { start: usize, length: usize },
+}
+
+/// Every state visited up to the point where the system repeats, which is enough to know the state at any step.
+#[derive(Debug, Clone)]
+pub struct Repetition<S> {
+    states: Vec<S>,
+    start: usize,
+    length: usize,
+}
+impl<S> Repetition<S> {
+    pub fn outcome(&self) -> Outcome {
+        match self.length {
+            1 => Outcome::Fixpoint { step: self.start },
+            length => Outcome::Cycle { start: self.start, length },
+        }
+    }
+
+    /// The first step that is part of the repeating states.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// The state after `step` steps, extrapolated through the cycle for steps that were never simulated.
+    pub fn state_at(&self, step: usize) -> &S {
+        if step < self.start {
+            &self.states[step]
+        } else {
+            &self.states[self.start + (step - self.start) % self.length]
+        }
+    }
+}
+
+/// Applies `step` to `initial` until a state repeats, identifying states by `key`. Gives up with `None` when no
+/// state has repeated after `max_steps` steps.
+pub fn detect_by_key<S, K, F, G>(initial: S, max_steps: usize, mut step: F, key: G) -> Option<Repetition<S>>
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: Fn(&S) -> K,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    for index in 0..=max_steps {
+        match seen.entry(key(&state)) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                return Some(Repetition {
+                    states,
+                    start,
+                    length: index - start,
+                });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(index);
+            }
+        }
+
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+
+    None
+}
+
+/// Applies `step` to `initial` until a state repeats. Gives up with `None` when no state has repeated after
+/// `max_steps` steps.
+pub fn detect<S, F>(initial: S, max_steps: usize, step: F) -> Option<Repetition<S>>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    detect_by_key(initial, max_steps, step, S::clone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn will_detect_fixpoint() {
+        let repetition = detect(20, 100, |&n| if n > 3 { n / 2 } else { n }).unwrap();
+        assert_eq!(repetition.outcome(), Outcome::Fixpoint { step: 3 });
+        assert_eq!(*repetition.state_at(1), 10);
+        assert_eq!(*repetition.state_at(1_000_000), 2);
+    }
+
+    #[test]
+    fn will_detect_cycle_and_extrapolate() {
+        let repetition = detect(0, 100, |&n| if n < 5 { n + 1 } else { 2 }).unwrap();
+        assert_eq!(repetition.outcome(), Outcome::Cycle { start: 2, length: 4 });
+        assert_eq!((repetition.start(), repetition.length()), (2, 4));
+        assert_eq!(*repetition.state_at(5), 5);
+        assert_eq!(*repetition.state_at(6), 2);
+        assert_eq!(*repetition.state_at(1003), 3);
+    }
+
+    #[test]
+    fn will_give_up_after_max_steps() {
+        assert!(detect(0u64, 10, |&n| n + 1).is_none());
+        assert!(detect(0u64, 10, |&n| (n + 1) % 10).is_some());
+    }
+
+    #[test]
+    fn will_identify_states_by_key() {
+        let repetition = detect_by_key(0.5f64, 100, |&n| -n, |n| n.to_bits()).unwrap();
+        assert_eq!(repetition.outcome(), Outcome::Cycle { start: 0, length: 2 });
+    }
+}
@@ -0,0 +1,306 @@
+use std::{borrow::Borrow, collections::{HashMap, VecDeque}, hash::Hash};
+
+/// Which way to follow the edges of a graph.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    /// From a node to the nodes its edges point at.
+    Forward,
+    /// From a node to the nodes with edges pointing at it.
+    Reverse,
+}
+
+/// A traversal that needs an acyclic graph ran into a cycle, which passes through `node`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Cycle {
+    pub node: usize,
+}
+
+/// A directed graph with weighted edges. Nodes are named by keys, which are interned into dense ids so that
+/// traversals index into vectors instead of hashing.
+#[derive(Debug, Default, Clone)]
+pub struct Graph<K, W> {
+    ids: HashMap<K, usize>,
+    keys: Vec<K>,
+    forward: Vec<Vec<(usize, W)>>,
+    reverse: Vec<Vec<(usize, W)>>,
+}
+impl<K: Hash + Eq + Clone, W: Copy> Graph<K, W> {
+    pub fn new() -> Graph<K, W> {
+        Graph {
+            ids: HashMap::new(),
+            keys: vec![],
+            forward: vec![],
+            reverse: vec![],
+        }
+    }
+
+    /// The id of the node named `key`, adding the node if it is new.
+    pub fn intern(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = self.keys.len();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        self.forward.push(vec![]);
+        self.reverse.push(vec![]);
+        id
+    }
+
+    pub fn id<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(key).copied()
+    }
+
+    pub fn key(&self, id: usize) -> &K {
+        &self.keys[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        self.forward[from].push((to, weight));
+        self.reverse[to].push((from, weight));
+    }
+
+    /// The edges leaving `id` in the given direction, as the node at the other end and the weight.
+    pub fn edges(&self, id: usize, direction: Direction) -> &[(usize, W)] {
+        match direction {
+            Direction::Forward => &self.forward[id],
+            Direction::Reverse => &self.reverse[id],
+        }
+    }
+
+    /// Every node other than `start` that can be reached from it, in breadth-first order. Each node and edge is
+    /// visited at most once.
+    pub fn reachable(&self, start: usize, direction: Direction) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        seen[start] = true;
+        let mut queue = VecDeque::from(vec![start]);
+        let mut reached = vec![];
+
+        while let Some(id) = queue.pop_front() {
+            for &(next, _) in self.edges(id, direction) {
+                if !seen[next] {
+                    seen[next] = true;
+                    reached.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        reached
+    }
+
+    /// `start` and every node reachable from it, ordered so that each node comes before the nodes its edges lead to.
+    /// Fails when a cycle can be reached from `start`, as no such order exists.
+    pub fn topological_order(&self, start: usize, direction: Direction) -> Result<Vec<usize>, Cycle> {
+        let mut nodes = vec![start];
+        nodes.extend(self.reachable(start, direction));
+        self.kahn(&nodes, direction)
+    }
+
+    /// Every node, ordered so that each node comes before the nodes its edges lead to, or the cycle preventing it.
+    pub fn topological_sort(&self, direction: Direction) -> Result<Vec<usize>, Cycle> {
+        self.kahn(&(0..self.len()).collect::<Vec<_>>(), direction)
+    }
+
+    /// Kahn's algorithm over `nodes`, which must include every node their edges lead to. Nodes on or behind a cycle
+    /// never lose all their incoming edges, so they are left over.
+    fn kahn(&self, nodes: &[usize], direction: Direction) -> Result<Vec<usize>, Cycle> {
+        let mut incoming = vec![0; self.len()];
+        for &id in nodes {
+            for &(next, _) in self.edges(id, direction) {
+                incoming[next] += 1;
+            }
+        }
+
+        let mut order: Vec<usize> = nodes.iter().copied().filter(|&id| incoming[id] == 0).collect();
+        let mut done = 0;
+        while done < order.len() {
+            let id = order[done];
+            done += 1;
+            for &(next, _) in self.edges(id, direction) {
+                incoming[next] -= 1;
+                if incoming[next] == 0 {
+                    order.push(next);
+                }
+            }
+        }
+
+        match nodes.iter().find(|&&id| incoming[id] > 0) {
+            None => Ok(order),
+            Some(&left) => Err(self.cycle_behind(left, &incoming, direction)),
+        }
+    }
+
+    /// Walks back from a node Kahn's algorithm left over, which always has a left over node before it, until a node
+    /// repeats. That node lies on a cycle.
+    fn cycle_behind(&self, mut id: usize, incoming: &[usize], direction: Direction) -> Cycle {
+        let backwards = match direction {
+            Direction::Forward => Direction::Reverse,
+            Direction::Reverse => Direction::Forward,
+        };
+        let mut seen = vec![false; self.len()];
+        while !seen[id] {
+            seen[id] = true;
+            id = self.edges(id, backwards).iter().map(|&(before, _)| before).find(|&before| incoming[before] > 0).unwrap();
+        }
+        Cycle { node: id }
+    }
+
+    /// A path with the fewest edges from `from` to `to`, listing both ends, or `None` when `to` can not be reached.
+    pub fn shortest_path(&self, from: usize, to: usize, direction: Direction) -> Option<Vec<usize>> {
+        let mut previous: Vec<Option<usize>> = vec![None; self.len()];
+        let mut queue = VecDeque::from(vec![from]);
+
+        while let Some(id) = queue.pop_front() {
+            if id == to {
+                let mut path = vec![to];
+                let mut id = to;
+                while let Some(before) = previous[id] {
+                    path.push(before);
+                    id = before;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &(next, _) in self.edges(id, direction) {
+                if next != from && previous[next].is_none() {
+                    previous[next] = Some(id);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Folds the nodes reachable from `start` bottom-up: the value of a node is `combine` applied to the node and the
+    /// values at the other end of its edges. Each node is computed once, so shared descendants cost nothing extra.
+    /// Fails when a cycle can be reached from `start`, as its nodes would depend on their own value.
+    pub fn fold<T, F>(&self, start: usize, direction: Direction, combine: F) -> Result<T, Cycle>
+    where
+        T: Clone,
+        F: Fn(usize, &[(T, W)]) -> T,
+    {
+        let mut values: Vec<Option<T>> = vec![None; self.len()];
+        let mut visiting = vec![false; self.len()];
+        let mut stack = vec![(start, false)];
+
+        while let Some((id, expanded)) = stack.pop() {
+            if values[id].is_some() {
+                continue;
+            }
+            let edges = self.edges(id, direction);
+            if expanded {
+                let children: Vec<(T, W)> = edges.iter()
+                    .map(|&(next, weight)| (values[next].clone().expect("Children should be folded before their parent"), weight))
+                    .collect();
+                values[id] = Some(combine(id, &children));
+            } else if visiting[id] {
+                // Everything above the expanded entry of `id` on the stack descends from it, so `id` reaches itself.
+                return Err(Cycle { node: id });
+            } else {
+                visiting[id] = true;
+                stack.push((id, true));
+                stack.extend(edges.iter().filter(|&&(next, _)| values[next].is_none()).map(|&(next, _)| (next, false)));
+            }
+        }
+        Ok(values[start].take().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -> b (2), a -> c (3), b -> d (4), c -> d (5)
+    fn diamond() -> Graph<&'static str, u32> {
+        let mut graph = Graph::new();
+        for &(from, to, weight) in &[("a", "b", 2), ("a", "c", 3), ("b", "d", 4), ("c", "d", 5)] {
+            let (from, to) = (graph.intern(from), graph.intern(to));
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn will_intern_keys() {
+        let mut graph = diamond();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.id("c"), Some(2));
+        assert_eq!(graph.id("e"), None);
+        assert_eq!(graph.intern("c"), 2);
+        assert_eq!(*graph.key(3), "d");
+        assert_eq!(graph.edges(3, Direction::Reverse), &[(1, 4), (2, 5)]);
+    }
+
+    #[test]
+    fn will_find_reachable_nodes() {
+        let graph = diamond();
+        let names = |ids: Vec<usize>| ids.into_iter().map(|id| *graph.key(id)).collect::<Vec<_>>();
+        assert_eq!(names(graph.reachable(0, Direction::Forward)), vec!["b", "c", "d"]);
+        assert_eq!(names(graph.reachable(3, Direction::Reverse)), vec!["b", "c", "a"]);
+        assert!(graph.reachable(3, Direction::Forward).is_empty());
+    }
+
+    #[test]
+    fn will_order_topologically() {
+        let graph = diamond();
+        let order = graph.topological_order(0, Direction::Forward).unwrap();
+        assert_eq!((order.len(), order[0], order[3]), (4, 0, 3));
+        assert_eq!(graph.topological_order(1, Direction::Forward), Ok(vec![1, 3]));
+        assert_eq!(graph.topological_order(3, Direction::Reverse).unwrap()[0], 3);
+        assert_eq!(graph.topological_sort(Direction::Reverse).unwrap()[0], 3);
+    }
+
+    #[test]
+    fn will_reject_cycles() {
+        let mut graph = diamond();
+        let (c, e) = (graph.intern("c"), graph.intern("e"));
+        graph.add_edge(e, c, 1);
+        graph.add_edge(graph.id("d").unwrap(), e, 1);
+
+        let on_cycle = |cycle: Cycle| ["c", "d", "e"].contains(graph.key(cycle.node));
+        assert!(on_cycle(graph.topological_order(0, Direction::Forward).unwrap_err()));
+        assert!(on_cycle(graph.topological_sort(Direction::Reverse).unwrap_err()));
+        assert!(on_cycle(graph.fold(0, Direction::Forward, |_, _: &[((), u32)]| ()).unwrap_err()));
+        assert_eq!(graph.topological_order(1, Direction::Reverse), Ok(vec![1, 0]));
+        assert_eq!(graph.fold(1, Direction::Reverse, |_, parents: &[(u32, u32)]| parents.len() as u32), Ok(1));
+    }
+
+    #[test]
+    fn will_find_shortest_path() {
+        let mut graph = diamond();
+        assert_eq!(graph.shortest_path(0, 3, Direction::Forward), Some(vec![0, 1, 3]));
+        assert_eq!(graph.shortest_path(3, 2, Direction::Reverse), Some(vec![3, 2]));
+        assert_eq!(graph.shortest_path(2, 2, Direction::Forward), Some(vec![2]));
+        assert_eq!(graph.shortest_path(3, 0, Direction::Forward), None);
+
+        let (d, a) = (graph.intern("d"), graph.intern("a"));
+        graph.add_edge(d, a, 1);
+        assert_eq!(graph.shortest_path(3, 2, Direction::Forward), Some(vec![3, 0, 2]));
+    }
+
+    #[test]
+    fn will_fold_shared_descendants_once() {
+        let graph = diamond();
+        let paths = graph.fold(0, Direction::Forward, |_, children: &[(u64, u32)]| {
+            children.iter().map(|&(paths, _)| paths).sum::<u64>().max(1)
+        }).unwrap();
+        assert_eq!(paths, 2);
+
+        let weighted = graph.fold(0, Direction::Forward, |_, children: &[(u32, u32)]| {
+            children.iter().map(|&(inside, weight)| weight * (inside + 1)).sum()
+        }).unwrap();
+        assert_eq!(weighted, 2 * 5 + 3 * 6);
+    }
+}
@@ -0,0 +1,279 @@
+use std::ops::{Index, IndexMut};
+use crate::util::parser::{parse_grid_rows, ParseError};
+
+/// Which of the surrounding cells count as neighbours.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Neighbourhood {
+    /// The four orthogonally adjacent cells.
+    Orthogonal,
+    /// All eight surrounding cells, including diagonals.
+    Surrounding,
+}
+impl Neighbourhood {
+    pub fn directions(&self) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::Orthogonal => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Neighbourhood::Surrounding => &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)],
+        }
+    }
+}
+
+/// What lies beyond the edges of a grid.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Edge<T> {
+    /// Nothing; positions outside the grid are skipped.
+    Clip,
+    /// The grid repeats infinitely in every direction.
+    Wrap,
+    /// Every position outside the grid holds the given value.
+    Default(T),
+}
+
+/// A rectangular map of cells stored row by row, addressed by `(x, y)` with the origin in the top left.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid<u8> {
+    /// Reads a map of single character cells, one row per line, where every row must have the same width.
+    pub fn parse(input: &str, is_cell: fn(u8) -> bool) -> Result<Grid<u8>, ParseError> {
+        let rows = parse_grid_rows(input.as_bytes(), is_cell)?;
+        Ok(Grid {
+            width: rows[0].len(),
+            height: rows.len(),
+            data: rows.concat(),
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, data: Vec<T>) -> Grid<T> {
+        assert_eq!(data.len(), width * height, "Expected {} cells for a {}x{} grid", width * height, width, height);
+        Grid {
+            data,
+            width,
+            height,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where T: Clone
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// The cell at `(x, y)`, or `None` when it lies outside the grid.
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        if self.contains(x, y) {
+            Some(&self[(x as usize, y as usize)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self[(x as usize, y as usize)])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `(x, y)` as if the grid repeated infinitely in every direction.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// The cell at `(x, y)`, with positions outside the grid resolved according to `edge`.
+    pub fn get_with_edge<'a>(&'a self, x: isize, y: isize, edge: &'a Edge<T>) -> Option<&'a T> {
+        match edge {
+            Edge::Clip => self.get(x, y),
+            Edge::Wrap => Some(self.get_wrapping(x, y)),
+            Edge::Default(value) => Some(self.get(x, y).unwrap_or(value)),
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        self[(x, y)] = value;
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside a grid of width {}", x, self.width);
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every position in row order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell together with its position, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    /// The positions of the neighbours of `(x, y)` that lie inside the grid.
+    pub fn neighbour_positions(&self, x: usize, y: usize, neighbourhood: Neighbourhood) -> impl Iterator<Item = (usize, usize)> + '_ {
+        neighbourhood.directions().iter()
+            .map(move |&(dx, dy)| (x as isize + dx, y as isize + dy))
+            .filter(move |&(x, y)| self.contains(x, y))
+            .map(|(x, y)| (x as usize, y as usize))
+    }
+
+    /// The values of the neighbours of `(x, y)`, where neighbours beyond the edge are resolved according to `edge`.
+    pub fn neighbours<'a>(&'a self, x: usize, y: usize, neighbourhood: Neighbourhood, edge: &'a Edge<T>) -> impl Iterator<Item = &'a T> {
+        neighbourhood.directions().iter()
+            .filter_map(move |&(dx, dy)| self.get_with_edge(x as isize + dx, y as isize + dy, edge))
+    }
+
+    /// Walks from `(x, y)` in `direction` and returns the first cell matching `predicate`, or `None` when the ray
+    /// leaves the grid first. The starting cell itself is not considered.
+    pub fn cast<P>(&self, x: usize, y: usize, direction: (isize, isize), predicate: P) -> Option<((usize, usize), &T)>
+    where P: Fn(&T) -> bool
+    {
+        assert!(direction != (0, 0), "A ray needs a direction");
+        let (mut x, mut y) = (x as isize, y as isize);
+        loop {
+            x += direction.0;
+            y += direction.1;
+            let value = self.get(x, y)?;
+            if predicate(value) {
+                return Some(((x as usize, y as usize), value));
+            }
+        }
+    }
+
+    /// The first cell matching `predicate` in every direction of the neighbourhood, as seen from `(x, y)`.
+    pub fn visible<'a, P>(&'a self, x: usize, y: usize, neighbourhood: Neighbourhood, predicate: P) -> impl Iterator<Item = ((usize, usize), &'a T)>
+    where P: Fn(&T) -> bool + Copy + 'a
+    {
+        neighbourhood.directions().iter()
+            .filter_map(move |&direction| self.cast(x, y, direction, predicate))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where F: FnMut(&T) -> U
+    {
+        Grid::new(self.width, self.height, self.data.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "Column {} is outside a grid of width {}", x, self.width);
+        &self.data[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "Column {} is outside a grid of width {}", x, self.width);
+        &mut self.data[y * self.width + x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<u8> {
+        Grid::parse("ab.\n.cd\n", |c| c == b'.' || c.is_ascii_lowercase()).unwrap()
+    }
+
+    #[test]
+    fn will_parse_rows() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), b".cd");
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&b"ab."[..], &b".cd"[..]]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"bc");
+
+        let error = Grid::parse("ab\nabc", |c| c.is_ascii_lowercase()).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn will_check_bounds_or_wrap() {
+        let grid = example();
+        assert_eq!(grid.get(2, 1), Some(&b'd'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, -1), None);
+        assert_eq!(grid.get_wrapping(3, 0), &b'a');
+        assert_eq!(grid.get_wrapping(-1, -1), &b'd');
+    }
+
+    #[test]
+    fn will_iterate_and_mutate() {
+        let mut grid = example().map(|&c| c == b'.');
+        grid.set(0, 0, true);
+        *grid.get_mut(1, 1).unwrap() = true;
+
+        let open: Vec<(usize, usize)> = grid.iter().filter(|(_, &open)| open).map(|(position, _)| position).collect();
+        assert_eq!(open, vec![(0, 0), (2, 0), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn will_list_neighbours_inside_grid() {
+        let grid = example();
+        assert_eq!(grid.neighbour_positions(0, 0, Neighbourhood::Surrounding).collect::<Vec<_>>(), vec![(1, 0), (0, 1), (1, 1)]);
+        assert_eq!(grid.neighbour_positions(1, 1, Neighbourhood::Surrounding).count(), 5);
+        assert_eq!(grid.neighbour_positions(1, 1, Neighbourhood::Orthogonal).collect::<Vec<_>>(), vec![(1, 0), (0, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn will_resolve_neighbours_beyond_edges() {
+        let grid = example();
+        let neighbours = |edge| grid.neighbours(0, 0, Neighbourhood::Orthogonal, &edge).copied().collect::<Vec<u8>>();
+
+        assert_eq!(neighbours(Edge::Clip), b"b.");
+        assert_eq!(neighbours(Edge::Wrap), b"..b.");
+        assert_eq!(neighbours(Edge::Default(b'#')), b"##b.");
+    }
+
+    #[test]
+    fn will_cast_rays() {
+        let grid = Grid::parse("a..b
+....
+c..d", |c| c == b'.' || c.is_ascii_lowercase()).unwrap();
+        let is_letter = |c: &u8| c.is_ascii_lowercase();
+
+        assert_eq!(grid.cast(0, 0, (1, 0), is_letter), Some(((3, 0), &b'b')));
+        assert_eq!(grid.cast(0, 0, (1, 1), is_letter), None);
+        assert_eq!(grid.cast(1, 1, (-1, 1), is_letter), Some(((0, 2), &b'c')));
+
+        let visible: Vec<u8> = grid.visible(0, 0, Neighbourhood::Surrounding, is_letter).map(|(_, &c)| c).collect();
+        assert_eq!(visible, b"bc");
+    }
+}
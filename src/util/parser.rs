@@ -1,5 +1,5 @@
-
-use nom::{IResult, bytes::complete::take_while1, character::is_digit, combinator::map};
+use std::{error::Error, fmt};
+use nom::{AsBytes, IResult, InputLength, Parser, bytes::complete::take_while1, character::{complete::newline, is_digit}, combinator::{all_consuming, map, opt}, error::ErrorKind, multi::many1, sequence::terminated};
 
 pub fn parse_decimal_u32(i: &[u8]) -> IResult<&[u8], u32> {
     map(take_while1(is_digit), |digits: &[u8]| {
@@ -15,4 +15,105 @@ pub fn parse_decimal_u64(i: &[u8]) -> IResult<&[u8], u64> {
             number * 10 + (*c - b'0') as u64
         })
     })(i)
-}
\ No newline at end of file
+}
+
+const SNIPPET_LEN: usize = 40;
+
+/// A malformed puzzle input, located by 1-based line and column.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub message: String,
+}
+impl ParseError {
+    /// Creates an error for the position where `remaining` starts, which must be a subslice of `input`.
+    pub fn at(input: &[u8], remaining: &[u8], message: &str) -> ParseError {
+        let offset = (remaining.as_ptr() as usize).saturating_sub(input.as_ptr() as usize).min(input.len());
+        let line_start = input[..offset].iter().rposition(|&c| c == b'\n').map_or(0, |position| position + 1);
+        let line_end = input[offset..].iter().position(|&c| c == b'\n').map_or(input.len(), |position| offset + position);
+
+        ParseError {
+            line: input[..offset].iter().filter(|&&c| c == b'\n').count() + 1,
+            column: offset - line_start + 1,
+            snippet: String::from_utf8_lossy(&input[offset..line_end.min(offset + SNIPPET_LEN)]).into_owned(),
+            message: message.to_owned(),
+        }
+    }
+
+    /// Converts a nom error produced while parsing (a subslice of) `input`.
+    pub fn from_nom<I: AsBytes>(input: &[u8], error: nom::Err<nom::error::Error<I>>) -> ParseError {
+        match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                let message = match error.code {
+                    ErrorKind::Eof => "unexpected input".to_owned(),
+                    code => format!("invalid input ({})", code.description()),
+                };
+                ParseError::at(input, error.input.as_bytes(), &message)
+            },
+            nom::Err::Incomplete(_) => ParseError::at(input, &input[input.len()..], "unexpected end of input"),
+        }
+    }
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {} at {:?}", self.line, self.column, self.message, self.snippet)
+    }
+}
+impl Error for ParseError {}
+
+/// Runs `parser` over the complete input. Failures, including leftover input, are reported with their location.
+pub fn parse_all<I, O, F>(input: I, parser: F) -> Result<O, ParseError>
+where
+    I: AsBytes + InputLength + Copy,
+    F: Parser<I, O, nom::error::Error<I>>,
+{
+    all_consuming(parser)(input)
+        .map(|(_, output)| output)
+        .map_err(|error| ParseError::from_nom(input.as_bytes(), error))
+}
+
+/// Parses a rectangular grid with one row per line, returning the rows. Every byte must satisfy `is_cell`.
+pub fn parse_grid_rows(input: &[u8], is_cell: fn(u8) -> bool) -> Result<Vec<&[u8]>, ParseError> {
+    let rows: Vec<&[u8]> = parse_all(input, many1(terminated(take_while1(is_cell), opt(newline))))?;
+
+    let width = rows[0].len();
+    match rows.iter().find(|row| row.len() != width) {
+        Some(row) => Err(ParseError::at(input, row, &format!("expected a row of width {}", width))),
+        None => Ok(rows),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_numbers(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse_all(input.as_bytes(), many1(terminated(parse_decimal_u32, opt(newline))))
+    }
+
+    #[test]
+    fn will_parse_all() {
+        assert_eq!(parse_numbers("1\n22\n333\n"), Ok(vec![1, 22, 333]));
+    }
+
+    #[test]
+    fn will_locate_error() {
+        assert_eq!(parse_numbers("1\n22\n3x3 and more\n4"), Err(ParseError {
+            line: 3,
+            column: 2,
+            snippet: "x3 and more".to_owned(),
+            message: "unexpected input".to_owned(),
+        }));
+        assert_eq!(parse_numbers("a").unwrap_err().to_string(), "line 1, column 1: invalid input (TakeWhile1) at \"a\"");
+    }
+
+    #[test]
+    fn will_parse_grid_rows() {
+        let is_cell = |c| c == b'.' || c == b'#';
+        assert_eq!(parse_grid_rows(b".#\n#.\n", is_cell), Ok(vec![&b".#"[..], &b"#."[..]]));
+        assert_eq!(parse_grid_rows(b".#\n#", is_cell).unwrap_err().line, 2);
+        assert_eq!(parse_grid_rows(b".#\n#x", is_cell).unwrap_err().column, 2);
+    }
+}
@@ -0,0 +1,68 @@
+use std::ops::Range;
+
+/// A seeded SplitMix64 generator, for reproducible inputs rather than anything that has to be unpredictable.
+#[derive(Debug, Clone)]
+pub struct Random {
+    state: u64,
+}
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, with a bias towards low numbers that is negligible for small bounds.
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Can not pick a number below 0");
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "Can not pick a number from an empty range");
+        range.start + self.below((range.end - range.start) as usize) as i64
+    }
+
+    /// True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn will_repeat_sequence_for_seed() {
+        let first: Vec<u64> = (0..5).scan(Random::new(7), |random, _| Some(random.next_u64())).collect();
+        let second: Vec<u64> = (0..5).scan(Random::new(7), |random, _| Some(random.next_u64())).collect();
+        assert_eq!(first, second);
+        assert_ne!(Random::new(8).next_u64(), first[0]);
+    }
+
+    #[test]
+    fn will_stay_within_bounds() {
+        let mut random = Random::new(2020);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let number = random.range(-2..3);
+            assert!((-2..3).contains(&number));
+            seen[(number + 2) as usize] = true;
+            assert!(random.below(3) < 3);
+        }
+        assert!(seen.iter().all(|&seen| seen));
+        assert!(!random.chance(0.0));
+        assert!(random.chance(1.0));
+    }
+}
@@ -0,0 +1,533 @@
+pub mod assemble;
+pub mod debug;
+pub mod disassemble;
+pub mod dot;
+pub mod generate;
+pub mod repair;
+
+use std::{collections::VecDeque, fmt};
+use crate::util::parser::{parse_all, ParseError};
+use nom::{IResult, branch::alt, bytes::complete::tag, character::complete::{digit1, newline, one_of}, combinator::{map, map_res, opt, value}, multi::many1, sequence::{preceded, separated_pair, terminated, tuple}};
+
+/// How many registers a machine has, named `a` onwards.
+pub const REGISTERS: usize = 8;
+
+/// One of the machine's registers. Register `a` is the accumulator that `acc` adds to.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Register(pub u8);
+impl Register {
+    pub const ACCUMULATOR: Register = Register(0);
+}
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", (b'a' + self.0) as char)
+    }
+}
+
+/// Where an instruction takes its value from.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Source {
+    /// The instruction's own amount.
+    Amount,
+    Register(Register),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum InstructionKind {
+    NOP,
+    ACC,
+    JMP,
+    /// Jumps by the amount when the register is zero.
+    JZ(Register),
+    /// Jumps by the amount when the register is not zero.
+    JNZ(Register),
+    SET(Register, Source),
+    ADD(Register, Source),
+    MUL(Register, Source),
+    /// Replaces the register by its remainder after division, which is never negative.
+    MOD(Register, Source),
+    /// Reads the next input value into the register.
+    IN(Register),
+    OUT(Source),
+}
+impl InstructionKind {
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            InstructionKind::NOP => "nop",
+            InstructionKind::ACC => "acc",
+            InstructionKind::JMP => "jmp",
+            InstructionKind::JZ(_) => "jz",
+            InstructionKind::JNZ(_) => "jnz",
+            InstructionKind::SET(..) => "set",
+            InstructionKind::ADD(..) => "add",
+            InstructionKind::MUL(..) => "mul",
+            InstructionKind::MOD(..) => "mod",
+            InstructionKind::IN(_) => "in",
+            InstructionKind::OUT(_) => "out",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Instruction {
+    pub kind: InstructionKind,
+    /// The jump offset or the value for [`Source::Amount`]; unused by the other instructions.
+    pub amount: i32,
+}
+
+impl Instruction {
+    /// Where this instruction jumps to when it sits at `pc` and the jump is taken, which may lie outside the
+    /// program.
+    pub fn jump_target(&self, pc: usize) -> Option<isize> {
+        match self.kind {
+            InstructionKind::JMP | InstructionKind::JZ(_) | InstructionKind::JNZ(_) => Some(pc as isize + self.amount as isize),
+            _ => None,
+        }
+    }
+
+    /// Every pc that may execute after this instruction when it sits at `pc`: both ways for a conditional jump,
+    /// otherwise the one place execution continues. Targets may lie outside the program.
+    pub fn successors(&self, pc: usize) -> impl Iterator<Item = isize> {
+        let next = match self.kind {
+            InstructionKind::JMP => None,
+            _ => Some(pc as isize + 1),
+        };
+        next.into_iter().chain(self.jump_target(pc))
+    }
+
+    fn fmt_source(&self, source: &Source, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match source {
+            Source::Amount => write!(f, "{:+}", self.amount),
+            Source::Register(register) => write!(f, "{}", register),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind.mnemonic())?;
+        match &self.kind {
+            InstructionKind::NOP | InstructionKind::ACC | InstructionKind::JMP => write!(f, " {:+}", self.amount),
+            InstructionKind::JZ(register) | InstructionKind::JNZ(register) => write!(f, " {} {:+}", register, self.amount),
+            InstructionKind::SET(register, source)
+            | InstructionKind::ADD(register, source)
+            | InstructionKind::MUL(register, source)
+            | InstructionKind::MOD(register, source) => {
+                write!(f, " {} ", register)?;
+                self.fmt_source(source, f)
+            }
+            InstructionKind::IN(register) => write!(f, " {}", register),
+            InstructionKind::OUT(source) => {
+                write!(f, " ")?;
+                self.fmt_source(source, f)
+            }
+        }
+    }
+}
+
+pub type Program = Vec<Instruction>;
+
+fn parse_decimal_number(i: &[u8]) -> IResult<&[u8], i32> {
+    map_res(digit1, |digits: &[u8]| std::str::from_utf8(digits).unwrap().parse())(i)
+}
+
+fn parse_signed_number(i: &[u8]) -> IResult<&[u8], i32> {
+    map(
+        tuple((
+            one_of("+-"),
+            parse_decimal_number,
+        )),
+        |(plus_or_minus, number)| {
+            let sign = if plus_or_minus == '-' { -1 } else { 1 };
+            sign * number
+        }
+    )(i)
+}
+
+fn parse_register(i: &[u8]) -> IResult<&[u8], Register> {
+    map(one_of("abcdefgh"), |name| Register(name as u8 - b'a'))(i)
+}
+
+/// A register, or a signed amount that ends up in [`Instruction::amount`].
+fn parse_source(i: &[u8]) -> IResult<&[u8], (Source, i32)> {
+    alt((
+        map(parse_register, |register| (Source::Register(register), 0)),
+        map(parse_signed_number, |amount| (Source::Amount, amount)),
+    ))(i)
+}
+
+pub fn parse_instruction(i: &[u8]) -> IResult<&[u8], Instruction> {
+    alt((
+        map(
+            separated_pair(
+                alt((
+                    value(InstructionKind::NOP, tag("nop")),
+                    value(InstructionKind::ACC, tag("acc")),
+                    value(InstructionKind::JMP, tag("jmp")),
+                )),
+                tag(" "),
+                parse_signed_number
+            ),
+            |(kind, amount)| Instruction { kind, amount }
+        ),
+        map(
+            tuple((
+                alt((
+                    value(InstructionKind::JZ as fn(Register) -> InstructionKind, tag("jz ")),
+                    value(InstructionKind::JNZ as fn(Register) -> InstructionKind, tag("jnz ")),
+                )),
+                terminated(parse_register, tag(" ")),
+                parse_signed_number,
+            )),
+            |(kind, register, amount)| Instruction { kind: kind(register), amount }
+        ),
+        map(
+            tuple((
+                alt((
+                    value(InstructionKind::SET as fn(Register, Source) -> InstructionKind, tag("set ")),
+                    value(InstructionKind::ADD as fn(Register, Source) -> InstructionKind, tag("add ")),
+                    value(InstructionKind::MUL as fn(Register, Source) -> InstructionKind, tag("mul ")),
+                    value(InstructionKind::MOD as fn(Register, Source) -> InstructionKind, tag("mod ")),
+                )),
+                terminated(parse_register, tag(" ")),
+                parse_source,
+            )),
+            |(kind, register, (source, amount))| Instruction { kind: kind(register, source), amount }
+        ),
+        map(preceded(tag("in "), parse_register), |register| Instruction { kind: InstructionKind::IN(register), amount: 0 }),
+        map(preceded(tag("out "), parse_source), |(source, amount)| Instruction { kind: InstructionKind::OUT(source), amount }),
+    ))(i)
+}
+
+/// Reads a program of one instruction per line, such as `acc -4`.
+pub fn parse_program(input: &str) -> Result<Program, ParseError> {
+    parse_all(input.as_bytes(), many1(terminated(parse_instruction, opt(newline))))
+}
+
+/// The example program of the day 8 puzzle, which loops with acc 5 and terminates with acc 8 once pc 7 is flipped.
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str =
+"nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+/// Why a machine stopped executing.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Halt {
+    /// Execution continued at the instruction directly after the last one.
+    Terminated,
+    /// The instruction at `pc` was about to be executed a second time.
+    InfiniteLoop { pc: usize },
+    /// The jump at `pc` went to `target`, which lies outside the program.
+    OutOfBounds { pc: usize, target: isize },
+    /// The `mod` at `pc` divided by zero.
+    DivisionByZero { pc: usize },
+    /// The `in` at `pc` found no input left; execution resumes there once more input is fed.
+    AwaitingInput { pc: usize },
+    /// The step budget ran out before the program halted by itself.
+    BudgetExhausted,
+}
+impl fmt::Display for Halt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Halt::Terminated => write!(f, "terminated"),
+            Halt::InfiniteLoop { pc } => write!(f, "infinite loop at pc {}", pc),
+            Halt::OutOfBounds { pc, target } => write!(f, "jump at pc {} out of bounds to {}", pc, target),
+            Halt::DivisionByZero { pc } => write!(f, "division by zero at pc {}", pc),
+            Halt::AwaitingInput { pc } => write!(f, "awaiting input at pc {}", pc),
+            Halt::BudgetExhausted => write!(f, "step budget exhausted"),
+        }
+    }
+}
+
+/// Executes a program one instruction at a time. By default it halts before any instruction would run twice, which
+/// is exact for the puzzle's instructions but also stops programs that loop on purpose with conditional jumps.
+#[derive(Debug, Clone)]
+pub struct Machine<'a> {
+    program: &'a [Instruction],
+    pc: usize,
+    registers: [i32; REGISTERS],
+    input: VecDeque<i32>,
+    output: Vec<i32>,
+    steps: usize,
+    visited: Vec<bool>,
+    detect_loops: bool,
+    halted: Option<Halt>,
+}
+impl<'a> Machine<'a> {
+    pub fn new(program: &'a [Instruction]) -> Machine<'a> {
+        Machine {
+            program,
+            pc: 0,
+            registers: [0; REGISTERS],
+            input: VecDeque::new(),
+            output: vec![],
+            steps: 0,
+            visited: vec![false; program.len()],
+            detect_loops: true,
+            halted: None,
+        }
+    }
+
+    /// Lets instructions run any number of times, so only the program itself or a step budget stops the machine.
+    pub fn without_loop_detection(mut self) -> Machine<'a> {
+        self.detect_loops = false;
+        self
+    }
+
+    pub fn with_input<I: IntoIterator<Item = i32>>(mut self, input: I) -> Machine<'a> {
+        self.feed(input);
+        self
+    }
+
+    /// Queues more values for `in`, resuming a machine that was awaiting input.
+    pub fn feed<I: IntoIterator<Item = i32>>(&mut self, input: I) {
+        self.input.extend(input);
+        if matches!(self.halted, Some(Halt::AwaitingInput { .. })) && !self.input.is_empty() {
+            self.halted = None;
+        }
+    }
+
+    pub fn program(&self) -> &'a [Instruction] {
+        self.program
+    }
+
+    /// The index of the instruction that executes next.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn accumulator(&self) -> i32 {
+        self.register(Register::ACCUMULATOR)
+    }
+
+    pub fn register(&self, register: Register) -> i32 {
+        self.registers[register.0 as usize]
+    }
+
+    pub fn registers(&self) -> &[i32; REGISTERS] {
+        &self.registers
+    }
+
+    /// Every value written by `out`, oldest first.
+    pub fn output(&self) -> &[i32] {
+        &self.output
+    }
+
+    /// How many instructions have been executed.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn halted(&self) -> Option<Halt> {
+        self.halted
+    }
+
+    fn value(&self, instruction: &Instruction, source: Source) -> i32 {
+        match source {
+            Source::Amount => instruction.amount,
+            Source::Register(register) => self.register(register),
+        }
+    }
+
+    /// Executes the next instruction, returning why the machine halted if it can not continue afterwards. A halted
+    /// machine stays halted.
+    pub fn step(&mut self) -> Option<Halt> {
+        if self.halted.is_some() {
+            return self.halted;
+        }
+        if self.pc == self.program.len() {
+            return self.halt(Halt::Terminated);
+        }
+        let instruction = self.program[self.pc];
+        if matches!(instruction.kind, InstructionKind::IN(_)) && self.input.is_empty() {
+            return self.halt(Halt::AwaitingInput { pc: self.pc });
+        }
+        self.visited[self.pc] = true;
+        self.steps += 1;
+
+        let jump = self.pc as isize + instruction.amount as isize;
+        let mut target = self.pc as isize + 1;
+        match instruction.kind {
+            InstructionKind::NOP => {}
+            InstructionKind::ACC => self.registers[0] = self.registers[0].wrapping_add(instruction.amount),
+            InstructionKind::JMP => target = jump,
+            InstructionKind::JZ(register) => if self.register(register) == 0 { target = jump },
+            InstructionKind::JNZ(register) => if self.register(register) != 0 { target = jump },
+            InstructionKind::SET(register, source) => self.registers[register.0 as usize] = self.value(&instruction, source),
+            InstructionKind::ADD(register, source) => {
+                let value = self.value(&instruction, source);
+                self.registers[register.0 as usize] = self.register(register).wrapping_add(value);
+            }
+            InstructionKind::MUL(register, source) => {
+                let value = self.value(&instruction, source);
+                self.registers[register.0 as usize] = self.register(register).wrapping_mul(value);
+            }
+            InstructionKind::MOD(register, source) => match self.value(&instruction, source) {
+                0 => return self.halt(Halt::DivisionByZero { pc: self.pc }),
+                value => self.registers[register.0 as usize] = self.register(register).wrapping_rem_euclid(value),
+            },
+            InstructionKind::IN(register) => self.registers[register.0 as usize] = self.input.pop_front().unwrap(),
+            InstructionKind::OUT(source) => {
+                let value = self.value(&instruction, source);
+                self.output.push(value);
+            }
+        };
+
+        if target < 0 || target as usize > self.program.len() {
+            return self.halt(Halt::OutOfBounds { pc: self.pc, target });
+        }
+
+        self.pc = target as usize;
+        if self.pc == self.program.len() {
+            self.halt(Halt::Terminated)
+        } else if self.detect_loops && self.visited[self.pc] {
+            self.halt(Halt::InfiniteLoop { pc: self.pc })
+        } else {
+            None
+        }
+    }
+
+    fn halt(&mut self, reason: Halt) -> Option<Halt> {
+        self.halted = Some(reason);
+        self.halted
+    }
+
+    /// Runs until the program halts by itself.
+    pub fn run(&mut self) -> Halt {
+        loop {
+            if let Some(halt) = self.step() {
+                return halt;
+            }
+        }
+    }
+
+    /// Runs until the program halts or `max_steps` more instructions have executed, whichever comes first.
+    pub fn run_with_budget(&mut self, max_steps: usize) -> Halt {
+        for _ in 0..max_steps {
+            if let Some(halt) = self.step() {
+                return halt;
+            }
+        }
+        Halt::BudgetExhausted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn will_parse_instruction() {
+        assert_eq!(
+            parse_instruction(r"nop +0".as_bytes()),
+            Ok((
+                r"".as_bytes(),
+                Instruction { kind: InstructionKind::NOP, amount: 0 }
+            ))
+        );
+        assert_eq!(
+            parse_instruction(r"acc +1".as_bytes()),
+            Ok((
+                r"".as_bytes(),
+                Instruction { kind: InstructionKind::ACC, amount: 1 }
+            ))
+        );
+        assert_eq!(
+            parse_instruction(r"acc -11".as_bytes()),
+            Ok((
+                r"".as_bytes(),
+                Instruction { kind: InstructionKind::ACC, amount: -11 }
+            ))
+        );
+        assert_eq!(
+            parse_instruction(r"jmp +1".as_bytes()),
+            Ok((
+                r"".as_bytes(),
+                Instruction { kind: InstructionKind::JMP, amount: 1 }
+            ))
+        );
+    }
+
+    #[test]
+    fn will_parse_extended_instructions() {
+        assert_eq!(parse("jnz c -2\nmul b a\nmod a +7\nin h\nout -1"), vec![
+            Instruction { kind: InstructionKind::JNZ(Register(2)), amount: -2 },
+            Instruction { kind: InstructionKind::MUL(Register(1), Source::Register(Register(0))), amount: 0 },
+            Instruction { kind: InstructionKind::MOD(Register(0), Source::Amount), amount: 7 },
+            Instruction { kind: InstructionKind::IN(Register(7)), amount: 0 },
+            Instruction { kind: InstructionKind::OUT(Source::Amount), amount: -1 },
+        ]);
+        assert!(parse_program("add i +1").is_err());
+        assert!(parse_program("jz +1").is_err());
+    }
+
+    #[test]
+    fn will_display_like_input() {
+        for line in &["nop +0", "acc -11", "jmp +4", "jz b -3", "set c +12", "add a d", "in e", "out f", "out +3"] {
+            assert_eq!(parse_instruction(line.as_bytes()).unwrap().1.to_string(), *line);
+        }
+    }
+
+    fn parse(input: &str) -> Program {
+        parse_program(input).unwrap()
+    }
+
+    #[test]
+    fn will_halt_on_infinite_loop() {
+        let program = parse(EXAMPLE);
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.run(), Halt::InfiniteLoop { pc: 1 });
+        assert_eq!((machine.accumulator(), machine.steps()), (5, 7));
+        assert_eq!(machine.step(), Some(Halt::InfiniteLoop { pc: 1 }));
+    }
+
+    #[test]
+    fn will_terminate_or_jump_out_of_bounds() {
+        let program = parse("acc +2\njmp +1");
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.step(), None);
+        assert_eq!((machine.pc(), machine.accumulator()), (1, 2));
+        assert_eq!(machine.run(), Halt::Terminated);
+        assert_eq!(machine.halted(), Some(Halt::Terminated));
+        assert_eq!(Machine::new(&[]).run(), Halt::Terminated);
+
+        let jumps = parse("acc +2\njmp -2\njmp +2");
+        assert_eq!(Machine::new(&jumps[2..]).run(), Halt::OutOfBounds { pc: 0, target: 2 });
+        assert_eq!(Machine::new(&jumps[1..]).run(), Halt::OutOfBounds { pc: 0, target: -2 });
+    }
+
+    #[test]
+    fn will_run_extended_program() {
+        // Outputs the factorial of every input and then the sum of their remainders modulo 5.
+        let program = parse("in b\nset c +1\nmul c b\nadd b -1\njnz b -2\nout c\nmod c +5\nadd a c\njmp -8");
+        let mut machine = Machine::new(&program).without_loop_detection().with_input(vec![3, 4]);
+        assert_eq!(machine.run(), Halt::AwaitingInput { pc: 0 });
+        assert_eq!(machine.output(), &[6, 24]);
+        assert_eq!(machine.accumulator(), 5);
+
+        machine.feed(vec![1]);
+        assert_eq!(machine.run(), Halt::AwaitingInput { pc: 0 });
+        assert_eq!((machine.output(), machine.accumulator()), (&[6, 24, 1][..], 6));
+
+        let program = parse("set b -7\nmod b +5\nmod b a");
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.run(), Halt::DivisionByZero { pc: 2 });
+        assert_eq!(machine.register(Register(1)), 3);
+    }
+
+    #[test]
+    fn will_respect_step_budget() {
+        let program = parse("acc +1\nacc +1\nacc +1");
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.run_with_budget(2), Halt::BudgetExhausted);
+        assert_eq!(machine.accumulator(), 2);
+        assert_eq!(machine.run_with_budget(1), Halt::Terminated);
+    }
+}
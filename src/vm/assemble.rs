@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use crate::util::parser::{parse_all, ParseError};
+use crate::vm::{parse_instruction, parse_register, Instruction, InstructionKind, Program};
+use nom::{IResult, branch::alt, bytes::complete::tag, character::complete::{alpha1, alphanumeric1, char, newline, not_line_ending, space0}, combinator::{map, opt, recognize}, multi::{many0, separated_list0}, sequence::{pair, preceded, terminated, tuple}};
+
+enum Statement<'a> {
+    Instruction(Instruction),
+    /// A jump whose amount is the distance to a label.
+    Jump(InstructionKind, &'a [u8]),
+}
+
+struct Line<'a> {
+    label: Option<&'a [u8]>,
+    statement: Option<Statement<'a>>,
+}
+
+fn parse_label(i: &[u8]) -> IResult<&[u8], &[u8]> {
+    recognize(pair(alt((alpha1, tag("_"))), many0(alt((alphanumeric1, tag("_"))))))(i)
+}
+
+fn parse_symbolic_jump(i: &[u8]) -> IResult<&[u8], (InstructionKind, &[u8])> {
+    alt((
+        map(preceded(tag("jmp "), parse_label), |label| (InstructionKind::JMP, label)),
+        map(preceded(tag("nop "), parse_label), |label| (InstructionKind::NOP, label)),
+        map(
+            tuple((tag("jz "), terminated(parse_register, tag(" ")), parse_label)),
+            |(_, register, label)| (InstructionKind::JZ(register), label)
+        ),
+        map(
+            tuple((tag("jnz "), terminated(parse_register, tag(" ")), parse_label)),
+            |(_, register, label)| (InstructionKind::JNZ(register), label)
+        ),
+    ))(i)
+}
+
+fn parse_line(i: &[u8]) -> IResult<&[u8], Line<'_>> {
+    map(
+        tuple((
+            space0,
+            opt(terminated(parse_label, pair(char(':'), space0))),
+            opt(alt((
+                map(parse_symbolic_jump, |(kind, label)| Statement::Jump(kind, label)),
+                map(parse_instruction, Statement::Instruction),
+            ))),
+            space0,
+            opt(preceded(char('#'), not_line_ending)),
+        )),
+        |(_, label, statement, _, _)| Line { label, statement }
+    )(i)
+}
+
+/// Lowers hand-written source into a program. Besides the plain instructions, lines may start with a `name:` label,
+/// end with a `#` comment or be blank, and jumps (including `nop`) may name a label instead of an offset. A label
+/// refers to the next instruction, or to the end of the program after the last one.
+pub fn assemble(input: &str) -> Result<Program, ParseError> {
+    let bytes = input.as_bytes();
+    let lines = parse_all(bytes, separated_list0(newline, parse_line))?;
+
+    let mut labels: HashMap<&[u8], usize> = HashMap::new();
+    let mut pc = 0;
+    for line in &lines {
+        if let Some(label) = line.label {
+            if labels.insert(label, pc).is_some() {
+                return Err(ParseError::at(bytes, label, "duplicate label"));
+            }
+        }
+        if line.statement.is_some() {
+            pc += 1;
+        }
+    }
+
+    lines.iter()
+        .filter_map(|line| line.statement.as_ref())
+        .enumerate()
+        .map(|(pc, statement)| match statement {
+            Statement::Instruction(instruction) => Ok(*instruction),
+            Statement::Jump(kind, label) => match labels.get(label) {
+                Some(&target) => Ok(Instruction { kind: *kind, amount: target as i32 - pc as i32 }),
+                None => Err(ParseError::at(bytes, label, "undefined label")),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{disassemble::disassemble, parse_program, EXAMPLE};
+
+    #[test]
+    fn will_resolve_labels() {
+        let program = assemble("# Sums the numbers from the input until a zero.
+
+start:  in b         # next number
+        jz b done
+        add a b
+        jmp start
+done:
+").unwrap();
+        assert_eq!(program, parse_program("in b\njz b +3\nadd a b\njmp -3").unwrap());
+    }
+
+    #[test]
+    fn will_assemble_disassembled_program() {
+        let program = parse_program(&format!("{}\njmp -20", EXAMPLE)).unwrap();
+        assert_eq!(assemble(&disassemble(&program)), Ok(program));
+    }
+
+    #[test]
+    fn will_locate_label_errors() {
+        let error = assemble("loop: acc +1\n  jmp end\n").unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (2, 7, "undefined label"));
+
+        let error = assemble("a: nop +0\na: nop a").unwrap_err();
+        assert_eq!((error.line, error.column, error.message.as_str()), (2, 1, "duplicate label"));
+
+        let error = assemble("acc +1\nacc one").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
@@ -0,0 +1,272 @@
+use std::{collections::BTreeSet, io::{self, BufRead, Write}};
+use crate::vm::{Halt, Machine, Register};
+
+/// Runs the machine until it halts, writing every executed instruction to `out` together with its pc and the
+/// accumulator after it ran. Like `Machine::run_with_budget`, `max_steps` limits how many instructions run.
+pub fn trace<W: Write>(machine: &mut Machine, out: &mut W, max_steps: Option<usize>) -> io::Result<Halt> {
+    let limit = max_steps.map(|max_steps| machine.steps().saturating_add(max_steps));
+    loop {
+        if matches!(limit, Some(limit) if machine.steps() >= limit) {
+            return Ok(Halt::BudgetExhausted);
+        }
+        let (pc, steps) = (machine.pc(), machine.steps());
+        let halt = machine.step();
+        if machine.steps() > steps {
+            let instruction = machine.program()[pc].to_string();
+            writeln!(out, "{:>5}  {:<8} acc {}", pc, instruction, machine.accumulator())?;
+        }
+        if let Some(halt) = halt {
+            return Ok(halt);
+        }
+    }
+}
+
+/// A single line typed into the debugger.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Command {
+    /// Execute this many instructions, stopping early when the machine halts.
+    Step(usize),
+    /// Run until the machine halts, reaches a breakpoint or uses up the step budget.
+    Continue,
+    Break(usize),
+    Delete(usize),
+    Print,
+    Help,
+    Quit,
+}
+impl Command {
+    pub fn parse(line: &str) -> Result<Command, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let parse_number = |word: &str| word.parse().map_err(|_| format!("Invalid number '{}'", word));
+
+        match words.as_slice() {
+            ["step"] | ["s"] | [] => Ok(Command::Step(1)),
+            ["step", count] | ["s", count] => Ok(Command::Step(parse_number(count)?)),
+            ["continue"] | ["c"] => Ok(Command::Continue),
+            ["break", pc] | ["b", pc] => Ok(Command::Break(parse_number(pc)?)),
+            ["delete", pc] | ["d", pc] => Ok(Command::Delete(parse_number(pc)?)),
+            ["print"] | ["p"] => Ok(Command::Print),
+            ["help"] | ["h"] => Ok(Command::Help),
+            ["quit"] | ["q"] => Ok(Command::Quit),
+            _ => Err(format!("Unknown command '{}', try 'help'", line.trim())),
+        }
+    }
+}
+
+const HELP: &str = "Commands:
+    step [n], s [n]     execute the next n instructions, 1 by default or on an empty line
+    continue, c         run until a breakpoint, the step budget or until the program halts
+    break <pc>, b <pc>  stop before executing the instruction at pc
+    delete <pc>, d <pc> remove the breakpoint at pc
+    print, p            show the machine state, set registers, output and breakpoints
+    help, h             show this message
+    quit, q             leave the debugger";
+
+/// Steps through a program interactively, stopping before instructions that have a breakpoint.
+#[derive(Debug, Clone)]
+pub struct Debugger<'a> {
+    machine: Machine<'a>,
+    breakpoints: BTreeSet<usize>,
+    max_steps: Option<usize>,
+}
+impl<'a> Debugger<'a> {
+    pub fn new(machine: Machine<'a>) -> Debugger<'a> {
+        Debugger {
+            machine,
+            breakpoints: BTreeSet::new(),
+            max_steps: None,
+        }
+    }
+
+    /// Stops every [`Command::Continue`] after at most `max_steps` instructions, so programs without loop detection
+    /// can not hang the debugger.
+    pub fn with_max_steps(mut self, max_steps: Option<usize>) -> Debugger<'a> {
+        self.max_steps = max_steps;
+        self
+    }
+
+    pub fn machine(&self) -> &Machine<'a> {
+        &self.machine
+    }
+
+    fn state(&self) -> String {
+        let next = match self.machine.program().get(self.machine.pc()) {
+            Some(instruction) => instruction.to_string(),
+            None => "end of program".to_owned(),
+        };
+        let mut state = format!("pc {}: {}, acc {}", self.machine.pc(), next, self.machine.accumulator());
+        // The accumulator is register `a`, so only the other registers are listed, and only while they are set.
+        for (register, &value) in self.machine.registers().iter().enumerate().skip(1).filter(|&(_, &value)| value != 0) {
+            state.push_str(&format!(", {} {}", Register(register as u8), value));
+        }
+        state.push_str(&format!(", {} steps", self.machine.steps()));
+        if !self.machine.output().is_empty() {
+            let output: Vec<String> = self.machine.output().iter().map(|value| value.to_string()).collect();
+            state.push_str(&format!(", output {}", output.join(" ")));
+        }
+        if let Some(halt) = self.machine.halted() {
+            state.push_str(&format!(", halted: {}", halt));
+        }
+        state
+    }
+
+    fn halted(&self, halt: Halt) -> String {
+        format!("Halted: {}, acc {}", halt, self.machine.accumulator())
+    }
+
+    /// Carries out a command and describes the outcome, or gives `None` for [`Command::Quit`].
+    pub fn execute(&mut self, command: Command) -> Option<String> {
+        let output = match command {
+            Command::Step(count) => {
+                for _ in 0..count {
+                    if let Some(halt) = self.machine.step() {
+                        return Some(self.halted(halt));
+                    }
+                }
+                self.state()
+            }
+            Command::Continue => {
+                let limit = self.max_steps.map(|max_steps| self.machine.steps().saturating_add(max_steps));
+                loop {
+                    if matches!(limit, Some(limit) if self.machine.steps() >= limit) {
+                        break self.halted(Halt::BudgetExhausted);
+                    }
+                    if let Some(halt) = self.machine.step() {
+                        break self.halted(halt);
+                    }
+                    if self.breakpoints.contains(&self.machine.pc()) {
+                        break format!("Breakpoint, {}", self.state());
+                    }
+                }
+            }
+            Command::Break(pc) => match pc < self.machine.program().len() {
+                true => {
+                    self.breakpoints.insert(pc);
+                    format!("Breakpoint at pc {}", pc)
+                }
+                false => format!("There is no instruction at pc {}", pc),
+            },
+            Command::Delete(pc) => match self.breakpoints.remove(&pc) {
+                true => format!("Deleted breakpoint at pc {}", pc),
+                false => format!("There is no breakpoint at pc {}", pc),
+            },
+            Command::Print => {
+                let breakpoints: Vec<String> = self.breakpoints.iter().map(|pc| pc.to_string()).collect();
+                match breakpoints.is_empty() {
+                    true => self.state(),
+                    false => format!("{}\nBreakpoints: {}", self.state(), breakpoints.join(", ")),
+                }
+            }
+            Command::Help => HELP.to_owned(),
+            Command::Quit => return None,
+        };
+        Some(output)
+    }
+
+    /// Reads commands from `input` until it ends or the user quits, writing a prompt and every response to `output`.
+    pub fn repl<R: BufRead, W: Write>(&mut self, input: R, output: &mut W) -> io::Result<()> {
+        writeln!(output, "{}", self.state())?;
+        write!(output, "(vm) ")?;
+        output.flush()?;
+
+        for line in input.lines() {
+            let response = match Command::parse(&line?) {
+                Ok(command) => self.execute(command),
+                Err(message) => Some(message),
+            };
+            match response {
+                Some(response) => writeln!(output, "{}", response)?,
+                None => return Ok(()),
+            }
+            write!(output, "(vm) ")?;
+            output.flush()?;
+        }
+        writeln!(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{parse_program, Program, EXAMPLE};
+
+    fn example() -> Program {
+        parse_program(EXAMPLE).unwrap()
+    }
+
+    #[test]
+    fn will_trace_until_loop() {
+        let program = example();
+        let mut out = vec![];
+        let halt = trace(&mut Machine::new(&program), &mut out, None).unwrap();
+
+        assert_eq!(halt, Halt::InfiniteLoop { pc: 1 });
+        let lines: Vec<String> = String::from_utf8(out).unwrap().lines().map(|line| line.to_owned()).collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "    0  nop +0   acc 0");
+        assert_eq!(lines[6], "    4  jmp -3   acc 5");
+
+        let mut out = vec![];
+        assert_eq!(trace(&mut Machine::new(&program), &mut out, Some(3)).unwrap(), Halt::BudgetExhausted);
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 3);
+    }
+
+    #[test]
+    fn will_parse_commands() {
+        assert_eq!(Command::parse(""), Ok(Command::Step(1)));
+        assert_eq!(Command::parse("s 3"), Ok(Command::Step(3)));
+        assert_eq!(Command::parse("break 4"), Ok(Command::Break(4)));
+        assert_eq!(Command::parse("  c "), Ok(Command::Continue));
+        assert!(Command::parse("break").is_err());
+        assert!(Command::parse("step x").is_err());
+    }
+
+    #[test]
+    fn will_stop_at_breakpoints() {
+        let program = example();
+        let mut debugger = Debugger::new(Machine::new(&program));
+
+        debugger.execute(Command::Break(7));
+        assert_eq!(debugger.execute(Command::Continue).unwrap(), "Breakpoint, pc 7: jmp -4, acc 2, 4 steps");
+        assert_eq!(debugger.execute(Command::Step(1)).unwrap(), "pc 3: acc +3, acc 2, 5 steps");
+        assert_eq!(debugger.execute(Command::Continue).unwrap(), "Halted: infinite loop at pc 1, acc 5");
+        assert_eq!(debugger.execute(Command::Quit), None);
+    }
+
+    #[test]
+    fn will_show_registers_and_output() {
+        let program = parse_program("in b\nset c +2\nmul c b\nout c\nadd b -3").unwrap();
+        let mut debugger = Debugger::new(Machine::new(&program).with_input(vec![3]));
+
+        assert_eq!(debugger.execute(Command::Step(4)).unwrap(), "pc 4: add b -3, acc 0, b 3, c 6, 4 steps, output 6");
+        assert_eq!(debugger.execute(Command::Step(1)).unwrap(), "Halted: terminated, acc 0");
+        assert_eq!(debugger.execute(Command::Print).unwrap(), "pc 5: end of program, acc 0, c 6, 5 steps, output 6, halted: terminated");
+    }
+
+    #[test]
+    fn will_stop_continue_at_step_budget() {
+        let program = parse_program("acc +1\njmp -1").unwrap();
+        let mut debugger = Debugger::new(Machine::new(&program).without_loop_detection()).with_max_steps(Some(5));
+
+        assert_eq!(debugger.execute(Command::Continue).unwrap(), "Halted: step budget exhausted, acc 3");
+        assert_eq!(debugger.execute(Command::Continue).unwrap(), "Halted: step budget exhausted, acc 5");
+        assert_eq!(debugger.machine().steps(), 10);
+    }
+
+    #[test]
+    fn will_run_session_from_input() {
+        let program = example();
+        let mut debugger = Debugger::new(Machine::new(&program));
+        let mut output = vec![];
+        debugger.repl("b 3\nc\np\nfoo\nq\ns\n".as_bytes(), &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "pc 0: nop +0, acc 0, 0 steps
+(vm) Breakpoint at pc 3
+(vm) Breakpoint, pc 3: acc +3, acc 2, 5 steps
+(vm) pc 3: acc +3, acc 2, 5 steps
+Breakpoints: 3
+(vm) Unknown command 'foo', try 'help'
+(vm) ");
+        assert_eq!(debugger.machine().steps(), 5);
+    }
+}
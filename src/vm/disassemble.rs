@@ -0,0 +1,86 @@
+use std::collections::BTreeSet;
+use crate::vm::{Instruction, InstructionKind};
+
+/// The name of the label in front of the instruction at `pc`.
+pub fn label(pc: usize) -> String {
+    format!("L{}", pc)
+}
+
+/// Every pc that a jump lands on, including the end of the program but not targets outside it.
+pub fn jump_targets(program: &[Instruction]) -> BTreeSet<usize> {
+    program.iter().enumerate()
+        .filter_map(|(pc, instruction)| instruction.jump_target(pc))
+        .filter(|&target| target >= 0 && target as usize <= program.len())
+        .map(|target| target as usize)
+        .collect()
+}
+
+/// Lists a program one instruction per line with its pc, replacing relative jumps by labels on their targets.
+/// Jumps that leave the program keep their offset and name the pc they would land on.
+pub fn disassemble(program: &[Instruction]) -> String {
+    let targets = jump_targets(program);
+    let mut lines = vec![];
+
+    for (pc, instruction) in program.iter().enumerate() {
+        if targets.contains(&pc) {
+            lines.push(format!("{}:", label(pc)));
+        }
+        let line = match instruction.jump_target(pc) {
+            Some(target) if target >= 0 && targets.contains(&(target as usize)) => {
+                let jump = match instruction.kind {
+                    InstructionKind::JZ(register) | InstructionKind::JNZ(register) => format!("{} {}", instruction.kind.mnemonic(), register),
+                    _ => instruction.kind.mnemonic().to_owned(),
+                };
+                format!("    {:<14}# {}", format!("{} {}", jump, label(target as usize)), pc)
+            }
+            Some(target) => format!("    {:<14}# {} -> {}", instruction.to_string(), pc, target),
+            None => format!("    {:<14}# {}", instruction.to_string(), pc),
+        };
+        lines.push(line);
+    }
+    if targets.contains(&program.len()) {
+        lines.push(format!("{}:", label(program.len())));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{parse_program, EXAMPLE};
+
+    #[test]
+    fn will_label_conditional_jumps() {
+        let program = parse_program("in b\nadd a b\nadd b -1\njnz b -2\nout a").unwrap();
+        assert_eq!(disassemble(&program),
+"    in b          # 0
+L1:
+    add a b       # 1
+    add b -1      # 2
+    jnz b L1      # 3
+    out a         # 4");
+    }
+
+    #[test]
+    fn will_label_jump_targets() {
+        let program = parse_program(&format!("{}\njmp +1\njmp -20", EXAMPLE)).unwrap();
+        assert_eq!(jump_targets(&program).into_iter().collect::<Vec<_>>(), vec![1, 3, 6, 10]);
+        assert_eq!(disassemble(&program),
+"    nop +0        # 0
+L1:
+    acc +1        # 1
+    jmp L6        # 2
+L3:
+    acc +3        # 3
+    jmp L1        # 4
+    acc -99       # 5
+L6:
+    acc +1        # 6
+    jmp L3        # 7
+    acc +6        # 8
+    jmp L10       # 9
+L10:
+    jmp -20       # 10 -> -10");
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use crate::vm::{Halt, Instruction, Machine, repair::{self, Repair}};
+
+/// The instructions of the cycle an unchanged program gets stuck in, or nothing when it halts otherwise.
+pub fn find_loop(program: &[Instruction]) -> HashSet<usize> {
+    let mut machine = Machine::new(program);
+    let mut executed = vec![];
+    let halt = loop {
+        executed.push(machine.pc());
+        if let Some(halt) = machine.step() {
+            break halt;
+        }
+    };
+
+    match halt {
+        Halt::InfiniteLoop { pc: start } => executed.into_iter().skip_while(|&pc| pc != start).collect(),
+        _ => HashSet::new(),
+    }
+}
+
+fn node(program: &[Instruction], target: isize) -> String {
+    match target {
+        target if target as usize == program.len() => "end".to_owned(),
+        target if target >= 0 && (target as usize) < program.len() => format!("n{}", target),
+        target => format!("\"out{}\"", target),
+    }
+}
+
+/// Writes the control-flow graph of a program in Graphviz DOT, with one node per instruction and an edge for every
+/// way execution can continue. The loop of the unchanged program is drawn in red, and the instruction that repairs
+/// it is filled green with a dashed edge to where it goes once flipped.
+pub fn control_flow_graph(program: &[Instruction]) -> String {
+    let cycle = find_loop(program);
+    let repair: Option<Repair> = match cycle.is_empty() {
+        true => None,
+        false => repair::repair_linear(program),
+    };
+
+    let mut lines = vec![
+        "digraph program {".to_owned(),
+        "    node [shape=box, fontname=monospace];".to_owned(),
+        "    end [shape=doublecircle];".to_owned(),
+    ];
+    let mut outside = HashSet::new();
+
+    for (pc, instruction) in program.iter().enumerate() {
+        let mut attributes = vec![format!("label=\"{}: {}\"", pc, instruction)];
+        if cycle.contains(&pc) {
+            attributes.push("color=red".to_owned());
+        }
+        if matches!(repair, Some(repair) if repair.pc == pc) {
+            attributes.push("style=filled, fillcolor=palegreen".to_owned());
+        }
+        lines.push(format!("    n{} [{}];", pc, attributes.join(", ")));
+    }
+
+    for (pc, instruction) in program.iter().enumerate() {
+        for target in instruction.successors(pc) {
+            if repair::within(program, target).is_none() && outside.insert(target) {
+                lines.push(format!("    {} [label=\"{}\", shape=plaintext];", node(program, target), target));
+            }
+            let in_loop = cycle.contains(&pc) && target >= 0 && cycle.contains(&(target as usize));
+            match in_loop {
+                true => lines.push(format!("    n{} -> {} [color=red];", pc, node(program, target))),
+                false => lines.push(format!("    n{} -> {};", pc, node(program, target))),
+            }
+        }
+    }
+
+    if let Some(repair) = repair {
+        let target = repair.replacement.successors(repair.pc).next().unwrap();
+        lines.push(format!("    n{} -> {} [style=dashed, color=green, label=\"{}\"];", repair.pc, node(program, target), repair.replacement));
+    }
+
+    lines.push("}".to_owned());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{parse_program, EXAMPLE};
+
+    #[test]
+    fn will_highlight_loop_and_repair() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let mut cycle: Vec<usize> = find_loop(&program).into_iter().collect();
+        cycle.sort_unstable();
+        assert_eq!(cycle, vec![1, 2, 3, 4, 6, 7]);
+
+        let dot = control_flow_graph(&program);
+        assert!(dot.starts_with("digraph program {\n"));
+        assert!(dot.contains("    n4 [label=\"4: jmp -3\", color=red];"));
+        assert!(dot.contains("    n7 [label=\"7: jmp -4\", color=red, style=filled, fillcolor=palegreen];"));
+        assert!(dot.contains("    n4 -> n1 [color=red];"));
+        assert!(dot.contains("    n8 -> end;"));
+        assert!(dot.contains("    n7 -> n8 [style=dashed, color=green, label=\"nop -4\"];"));
+    }
+
+    #[test]
+    fn will_name_targets_outside_program() {
+        let program = parse_program("jmp -2\njmp +0").unwrap();
+        let dot = control_flow_graph(&program);
+        assert!(find_loop(&[]).is_empty());
+        assert!(dot.contains("    \"out-2\" [label=\"-2\", shape=plaintext];"));
+        assert!(dot.contains("    n0 -> \"out-2\";"));
+    }
+}
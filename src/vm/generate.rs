@@ -0,0 +1,160 @@
+use crate::util::random::Random;
+use crate::vm::{Halt, Instruction, InstructionKind, Machine, Program, repair::{self, Repair}};
+
+/// A generated program together with the answers the puzzle expects for it.
+#[derive(Debug, Clone)]
+pub struct Generated {
+    pub program: Program,
+    /// The accumulator just before the unchanged program loops, the answer to part 1.
+    pub loop_accumulator: i32,
+    /// The only flip that makes the program terminate, holding the answer to part 2.
+    pub repair: Repair,
+}
+
+const MAX_ACC: i64 = 50;
+
+fn acc(random: &mut Random) -> Instruction {
+    Instruction { kind: InstructionKind::ACC, amount: random.range(-MAX_ACC..MAX_ACC + 1) as i32 }
+}
+
+fn jmp(pc: usize, target: usize) -> Instruction {
+    Instruction { kind: InstructionKind::JMP, amount: target as i32 - pc as i32 }
+}
+
+fn nop(pc: usize, target: usize) -> Instruction {
+    Instruction { kind: InstructionKind::NOP, amount: target as i32 - pc as i32 }
+}
+
+/// Removes `pc` from the unvisited instructions in constant time, where `position` locates each of them.
+fn take(pc: usize, unvisited: &mut Vec<usize>, position: &mut [Option<usize>]) {
+    let index = position[pc].take().unwrap();
+    unvisited.swap_remove(index);
+    if index < unvisited.len() {
+        position[unvisited[index]] = Some(index);
+    }
+}
+
+/// Generates a looping program of `size` instructions that terminates after exactly one `nop`/`jmp` flip.
+///
+/// The program is a head whose jumps, `nop` amounts included, all stay inside it, followed by a tail that only
+/// moves forwards. Nothing in the head reaches the tail unchanged, so execution walks a random path through the head
+/// until it loops. The only flip on that path that enters the tail is the repair: either the last instruction of the
+/// head, a `jmp` that falls through once flipped, or a `nop` on the path with an amount pointing into the tail.
+pub fn generate(size: usize, random: &mut Random) -> Generated {
+    assert!(size > 0, "A program needs at least one instruction");
+    let tail = size / 3;
+    let head = size - tail;
+    let last = head - 1;
+
+    let mut program: Vec<Option<Instruction>> = vec![None; size];
+    let mut unvisited: Vec<usize> = (1..last).collect();
+    let mut position: Vec<Option<usize>> = (0..size).map(|pc| if pc >= 1 && pc < last { Some(pc - 1) } else { None }).collect();
+
+    let length = match last {
+        0 => 1,
+        _ => 1 + random.below(last),
+    };
+    let mut path = vec![0];
+    let mut pc = 0;
+    while path.len() < length {
+        let next = if pc + 1 < last && position[pc + 1].is_some() && random.chance(0.6) {
+            program[pc] = Some(if random.chance(0.3) { nop(pc, random.below(head)) } else { acc(random) });
+            pc + 1
+        } else {
+            let next = *random.pick(&unvisited);
+            program[pc] = Some(jmp(pc, next));
+            next
+        };
+        take(next, &mut unvisited, &mut position);
+        path.push(next);
+        pc = next;
+    }
+
+    let falls_through: Vec<usize> = path[..path.len() - 1].iter()
+        .filter(|&&pc| matches!(program[pc], Some(instruction) if instruction.kind != InstructionKind::JMP))
+        .copied()
+        .collect();
+    let repair_pc = if !falls_through.is_empty() && random.chance(0.5) {
+        program[pc] = Some(jmp(pc, *random.pick(&path)));
+        program[last] = Some(jmp(last, random.below(head)));
+        let repair_pc = *random.pick(&falls_through);
+        program[repair_pc] = Some(nop(repair_pc, head + random.below(tail + 1)));
+        repair_pc
+    } else {
+        if pc != last {
+            program[pc] = Some(jmp(pc, last));
+            path.push(last);
+        }
+        program[last] = Some(jmp(last, *random.pick(&path)));
+        last
+    };
+
+    for (pc, instruction) in program.iter_mut().enumerate().filter(|(_, instruction)| instruction.is_none()) {
+        let roll = random.below(10);
+        *instruction = Some(match pc < head {
+            true if roll < 5 => acc(random),
+            true if roll < 7 => nop(pc, random.below(head)),
+            true => jmp(pc, random.below(head)),
+            false if roll < 6 => acc(random),
+            false if roll < 8 => nop(pc, random.below(size + 1)),
+            false => jmp(pc, pc + 1 + random.below(size - pc)),
+        });
+    }
+    let program: Program = program.into_iter().map(Option::unwrap).collect();
+
+    let mut machine = Machine::new(&program);
+    let halt = machine.run();
+    assert!(matches!(halt, Halt::InfiniteLoop { .. }), "Generated program should loop, got {:?}", halt);
+
+    let replacement = repair::flip(&program[repair_pc]).unwrap();
+    let mut repaired = program.clone();
+    repaired[repair_pc] = replacement;
+    let mut repaired_machine = Machine::new(&repaired);
+    assert_eq!(repaired_machine.run(), Halt::Terminated, "Generated repair should terminate");
+
+    Generated {
+        loop_accumulator: machine.accumulator(),
+        repair: Repair { pc: repair_pc, replacement, accumulator: repaired_machine.accumulator() },
+        program,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_repairs(program: &[Instruction]) -> usize {
+        (0..program.len())
+            .filter(|&pc| match repair::flip(&program[pc]) {
+                Some(replacement) => {
+                    let mut repaired = program.to_owned();
+                    repaired[pc] = replacement;
+                    Machine::new(&repaired).run() == Halt::Terminated
+                }
+                None => false,
+            })
+            .count()
+    }
+
+    #[test]
+    fn will_generate_single_flip_programs() {
+        let mut random = Random::new(2020);
+        for &size in &[1, 2, 3, 4, 10, 50, 300] {
+            for _ in 0..20 {
+                let generated = generate(size, &mut random);
+                assert_eq!(generated.program.len(), size);
+                assert_eq!(count_repairs(&generated.program), 1);
+                assert_eq!(repair::repair_brute_force(&generated.program), Some(generated.repair));
+                assert_eq!(repair::repair_linear(&generated.program), Some(generated.repair));
+            }
+        }
+    }
+
+    #[test]
+    fn will_repeat_program_for_seed() {
+        let first = generate(100, &mut Random::new(8));
+        let second = generate(100, &mut Random::new(8));
+        assert_eq!(first.program, second.program);
+        assert_eq!(first.loop_accumulator, second.loop_accumulator);
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::VecDeque;
+use crate::vm::{Halt, Instruction, InstructionKind, Machine};
+
+/// A single changed instruction that makes a looping program terminate.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Repair {
+    pub pc: usize,
+    pub replacement: Instruction,
+    /// The accumulator once the repaired program terminates.
+    pub accumulator: i32,
+}
+
+/// Swaps a `nop` for a `jmp` and the other way around, keeping the amount. Other instructions can not be flipped.
+pub fn flip(instruction: &Instruction) -> Option<Instruction> {
+    let kind = match instruction.kind {
+        InstructionKind::NOP => InstructionKind::JMP,
+        InstructionKind::JMP => InstructionKind::NOP,
+        _ => return None,
+    };
+    Some(Instruction { kind, amount: instruction.amount })
+}
+
+/// The pc `target` when that is inside the program or directly after it.
+pub(crate) fn within(program: &[Instruction], target: isize) -> Option<usize> {
+    if target < 0 || target as usize > program.len() {
+        None
+    } else {
+        Some(target as usize)
+    }
+}
+
+fn run_repaired(program: &[Instruction], pc: usize, replacement: Instruction) -> Option<Repair> {
+    let mut repaired = program.to_owned();
+    repaired[pc] = replacement;
+
+    let mut machine = Machine::new(&repaired);
+    match machine.run() {
+        Halt::Terminated => Some(Repair { pc, replacement, accumulator: machine.accumulator() }),
+        _ => None,
+    }
+}
+
+/// Tries flipping every `nop` and `jmp` in turn and runs the whole program each time, which takes quadratic time.
+pub fn repair_brute_force(program: &[Instruction]) -> Option<Repair> {
+    program.iter().enumerate()
+        .filter_map(|(pc, instruction)| flip(instruction).map(|replacement| (pc, replacement)))
+        .find_map(|(pc, replacement)| run_repaired(program, pc, replacement))
+}
+
+/// Finds the flip in linear time. Working backwards from the end of the program over the control-flow graph
+/// gives every instruction that terminates unchanged; the repair is then the first instruction on the looping
+/// path whose flipped successor is one of those. When several flips would work, this picks the one executed first
+/// rather than the one with the lowest index. Conditional jumps count as going both ways, so programs using them
+/// may get a flip that does not actually terminate, in which case there is no repair.
+pub fn repair_linear(program: &[Instruction]) -> Option<Repair> {
+    let end = program.len();
+    let mut predecessors: Vec<Vec<usize>> = vec![vec![]; end + 1];
+    for (pc, instruction) in program.iter().enumerate() {
+        for target in instruction.successors(pc).filter_map(|target| within(program, target)) {
+            predecessors[target].push(pc);
+        }
+    }
+
+    let mut terminates = vec![false; end + 1];
+    terminates[end] = true;
+    let mut queue: VecDeque<usize> = VecDeque::from(vec![end]);
+    while let Some(pc) = queue.pop_front() {
+        for &predecessor in &predecessors[pc] {
+            if !terminates[predecessor] {
+                terminates[predecessor] = true;
+                queue.push_back(predecessor);
+            }
+        }
+    }
+
+    let mut machine = Machine::new(program);
+    loop {
+        let pc = machine.pc();
+        if let Some(replacement) = program.get(pc).and_then(flip) {
+            let flipped = replacement.successors(pc).next().and_then(|target| within(program, target));
+            if matches!(flipped, Some(target) if terminates[target]) {
+                return run_repaired(program, pc, replacement);
+            }
+        }
+        if machine.step().is_some() {
+            return None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::{parse_program, EXAMPLE};
+
+    #[test]
+    fn will_flip_nop_and_jmp() {
+        let jump = Instruction { kind: InstructionKind::JMP, amount: -3 };
+        assert_eq!(flip(&jump), Some(Instruction { kind: InstructionKind::NOP, amount: -3 }));
+        assert_eq!(flip(&Instruction { kind: InstructionKind::ACC, amount: 1 }), None);
+    }
+
+    #[test]
+    fn repairs_agree_on_example() {
+        let program = parse_program(EXAMPLE).unwrap();
+        let expected = Repair { pc: 7, replacement: Instruction { kind: InstructionKind::NOP, amount: -4 }, accumulator: 8 };
+        assert_eq!(repair_brute_force(&program), Some(expected));
+        assert_eq!(repair_linear(&program), Some(expected));
+    }
+
+    #[test]
+    fn will_not_repair_beyond_one_flip() {
+        let program = parse_program("jmp +0\njmp -1").unwrap();
+        assert_eq!(repair_brute_force(&program), None);
+        assert_eq!(repair_linear(&program), None);
+    }
+}